@@ -0,0 +1,166 @@
+use std::{fs, path::Path};
+use eframe::egui;
+use crate::items::Item;
+
+/// The most items a list can have, so that a big file can't bring the
+/// program to a halt.
+const MAX_ITEMS: usize = 100_000;
+
+/// How the items of a list are written down.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Format {
+    /// Separated by whitespace or commas.
+    Plain,
+    /// Separated by commas or new lines, and maybe in double quotes.
+    Csv,
+    /// A JSON array of numbers or strings.
+    Json,
+}
+
+impl Format {
+    fn from_file_name(name: &str) -> Self {
+        let extension = Path::new(name).extension().and_then(|extension| extension.to_str()).unwrap_or("");
+
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Self::Csv,
+            "json" => Self::Json,
+            _ => Self::Plain,
+        }
+    }
+}
+
+/// Read a list that was typed in, with the items separated by whitespace or
+/// commas.
+pub fn parse_list<T: Item>(text: &str) -> Result<Vec<T>, String> {
+    parse_in_format(text, Format::Plain)
+}
+
+/// Read a list from a file, going by its extension for the format.
+pub fn read_file<T: Item>(path: &Path) -> Result<Vec<T>, String> {
+    let name = path.display();
+    let text = fs::read_to_string(path).map_err(|error| format!("Couldn't read {name}: {error}"))?;
+
+    parse_in_format(&text, Format::from_file_name(&path.to_string_lossy()))
+        .map_err(|error| format!("{name}: {error}"))
+}
+
+/// Read a list from a file that was dropped onto the window. Native builds
+/// get the path, but the web only gets the contents.
+pub fn read_dropped_file<T: Item>(file: &egui::DroppedFile) -> Result<Vec<T>, String> {
+    if let Some(path) = &file.path {
+        return read_file(path);
+    }
+
+    let Some(bytes) = &file.bytes else {
+        return Err(format!("Couldn't read {}", file.name));
+    };
+    let text = std::str::from_utf8(bytes).map_err(|_| format!("{} isn't a text file", file.name))?;
+
+    parse_in_format(text, Format::from_file_name(&file.name))
+        .map_err(|error| format!("{}: {error}", file.name))
+}
+
+fn parse_in_format<T: Item>(text: &str, format: Format) -> Result<Vec<T>, String> {
+    let fields = match format {
+        Format::Plain => text
+            .split(|character: char| character.is_whitespace() || character == ',')
+            .map(str::to_string)
+            .collect(),
+        Format::Csv => csv_fields(text),
+        Format::Json => json_fields(text)?,
+    };
+
+    let fields = fields.iter().filter(|field| !field.is_empty()).collect::<Vec<_>>();
+    if fields.is_empty() {
+        return Err("There are no items in the list".to_string());
+    }
+    if fields.len() > MAX_ITEMS {
+        return Err(format!("There are {} items in the list, which is more than the {MAX_ITEMS} a list can have", fields.len()));
+    }
+
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| T::parse(field).map_err(|error| format!("Item {}: {error}", index + 1)))
+        .collect()
+}
+
+/// The fields of a CSV file. Quoted fields can hold commas, new lines and
+/// doubled up quotes.
+fn csv_fields(text: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut characters = text.chars().peekable();
+    while let Some(character) = characters.next() {
+        match (character, quoted) {
+            ('"', true) if characters.peek() == Some(&'"') => {
+                field.push('"');
+                characters.next();
+            },
+            ('"', _) => quoted = !quoted,
+            (',' | '\n', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(character),
+        }
+    }
+    fields.push(field.trim().to_string());
+
+    fields
+}
+
+/// The items of a JSON array, written out the way they'd be typed in.
+fn json_fields(text: &str) -> Result<Vec<String>, String> {
+    let json = serde_json::from_str::<serde_json::Value>(text).map_err(|error| format!("Invalid JSON: {error}"))?;
+    let serde_json::Value::Array(items) = json else {
+        return Err("The JSON has to be an array of the items".to_string());
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| match item {
+            serde_json::Value::Number(number) => Ok(number.to_string()),
+            serde_json::Value::String(string) => Ok(string),
+            _ => Err(format!("Item {} of the JSON array isn't a number or a string", index + 1)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_lists_are_turned_down() {
+        assert_eq!(parse_list::<usize>(" , \n "), Err("There are no items in the list".to_string()));
+    }
+
+    #[test]
+    fn bad_items_are_reported_by_their_place_in_the_list() {
+        let error = parse_list::<usize>("3, 1 x 2").unwrap_err();
+
+        assert!(error.starts_with("Item 3: "), "{error}");
+    }
+
+    #[test]
+    fn quoted_csv_fields_keep_their_commas_and_quotes() {
+        let list = parse_in_format::<String>("\"a, b\",c\n\"say \"\"hi\"\"\"", Format::Csv);
+
+        assert_eq!(list, Ok(vec!["a, b".to_string(), "c".to_string(), "say \"hi\"".to_string()]));
+    }
+
+    #[test]
+    fn json_has_to_be_an_array() {
+        let error = parse_in_format::<usize>("{\"items\": [1, 2]}", Format::Json).unwrap_err();
+
+        assert_eq!(error, "The JSON has to be an array of the items");
+    }
+
+    #[test]
+    fn lists_that_are_too_long_are_turned_down() {
+        let error = parse_list::<usize>(&"1 ".repeat(MAX_ITEMS + 1)).unwrap_err();
+
+        assert!(error.contains(&MAX_ITEMS.to_string()), "{error}");
+        assert_eq!(parse_list::<usize>(&"1 ".repeat(MAX_ITEMS)).map(|list| list.len()), Ok(MAX_ITEMS));
+    }
+}
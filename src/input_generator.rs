@@ -0,0 +1,232 @@
+use rand::prelude::*;
+
+/// The kinds of list that can be generated. The values always stay within
+/// `1..=length` so that lists of the same length are drawn at the same scale.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Preset {
+    Random,
+    Reversed,
+    Sorted,
+    /// Sorted, then a few random pairs swapped.
+    NearlySorted,
+    /// Only a handful of different values, in a random order.
+    FewUnique,
+    /// A few runs that each climb from the bottom to the top.
+    Sawtooth,
+    /// Climbing up to the middle and back down again.
+    OrganPipe,
+    /// Random values bunched up around the middle.
+    Gaussian,
+    /// Random values bunched up at the bottom, with a long tail to the top.
+    Exponential,
+}
+
+impl Preset {
+    pub const ALL: [Self; 9] = [
+        Self::Random,
+        Self::Reversed,
+        Self::Sorted,
+        Self::NearlySorted,
+        Self::FewUnique,
+        Self::Sawtooth,
+        Self::OrganPipe,
+        Self::Gaussian,
+        Self::Exponential,
+    ];
+
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Random => "Random",
+            Self::Reversed => "Reversed",
+            Self::Sorted => "Sorted",
+            Self::NearlySorted => "Nearly Sorted",
+            Self::FewUnique => "Few Unique",
+            Self::Sawtooth => "Sawtooth",
+            Self::OrganPipe => "Organ Pipe",
+            Self::Gaussian => "Gaussian",
+            Self::Exponential => "Exponential",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct InputGenerator {
+    pub preset: Preset,
+    /// How many pairs a nearly sorted list has swapped.
+    pub swaps: usize,
+}
+
+impl Default for InputGenerator {
+    fn default() -> Self {
+        Self {
+            preset: Preset::Random,
+            swaps: 3,
+        }
+    }
+}
+
+impl InputGenerator {
+    pub fn generate(&self, length: usize, rng: &mut impl Rng) -> Vec<usize> {
+        const TEETH: usize = 4;
+        const UNIQUE_VALUES: usize = 5;
+
+        let scale = length as f64;
+        let clamp = |value: f64| (value.round() as usize).clamp(1, length);
+
+        match self.preset {
+            Preset::Random => {
+                let mut list = (1..=length).collect::<Vec<_>>();
+                list.shuffle(rng);
+                list
+            },
+            Preset::Reversed => (1..=length).rev().collect(),
+            Preset::Sorted => (1..=length).collect(),
+            Preset::NearlySorted => {
+                let mut list = (1..=length).collect::<Vec<_>>();
+                if length > 1 {
+                    for _ in 0..self.swaps {
+                        list.swap(rng.gen_range(0..length), rng.gen_range(0..length));
+                    }
+                }
+                list
+            },
+            Preset::FewUnique => {
+                let levels = UNIQUE_VALUES.min(length);
+                let mut list = (0..length)
+                    .map(|index| (index * levels / length + 1) * length / levels)
+                    .collect::<Vec<_>>();
+                list.shuffle(rng);
+                list
+            },
+            // Dealing the values out between the teeth makes each tooth climb
+            // all the way up
+            Preset::Sawtooth => (0..TEETH)
+                .flat_map(|tooth| (tooth + 1..=length).step_by(TEETH))
+                .collect(),
+            Preset::OrganPipe => (1..=length)
+                .step_by(2)
+                .chain((1..=length / 2).rev().map(|half| half * 2))
+                .collect(),
+            // Box-Muller, which turns two even random numbers into a normally
+            // distributed one
+            Preset::Gaussian => (0..length)
+                .map(|_| {
+                    let (first, second) = (1.0 - rng.gen::<f64>(), rng.gen::<f64>());
+                    let normal = (-2.0 * first.ln()).sqrt() * (std::f64::consts::TAU * second).cos();
+
+                    clamp(normal.mul_add(scale / 6.0, scale / 2.0))
+                })
+                .collect(),
+            Preset::Exponential => (0..length)
+                .map(|_| clamp((-(1.0 - rng.gen::<f64>()).ln()).mul_add(scale / 5.0, 1.0)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(preset: Preset, length: usize) -> Vec<usize> {
+        let generator = InputGenerator { preset, ..InputGenerator::default() };
+
+        generator.generate(length, &mut StdRng::seed_from_u64(3))
+    }
+
+    fn is_permutation(list: &[usize]) -> bool {
+        let mut sorted = list.to_vec();
+        sorted.sort_unstable();
+
+        sorted.into_iter().eq(1..=list.len())
+    }
+
+    /// How many times the list goes down from one value to the next.
+    fn descents(list: &[usize]) -> usize {
+        list.windows(2).filter(|pair| pair[0] > pair[1]).count()
+    }
+
+    #[test]
+    fn presets_are_as_long_as_asked_and_stay_in_range() {
+        for preset in Preset::ALL {
+            for length in [0, 1, 2, 7, 100] {
+                let list = generate(preset, length);
+
+                assert_eq!(list.len(), length, "{}", preset.get_name());
+                assert!(list.iter().all(|value| (1..=length).contains(value)), "{}", preset.get_name());
+                assert_eq!(list, generate(preset, length), "{} isn't the same for the same seed", preset.get_name());
+            }
+        }
+    }
+
+    #[test]
+    fn random_is_shuffled() {
+        let list = generate(Preset::Random, 100);
+
+        assert!(is_permutation(&list));
+        assert!(descents(&list) > 0);
+    }
+
+    #[test]
+    fn reversed_and_sorted_go_all_one_way() {
+        assert!(generate(Preset::Reversed, 100).into_iter().eq((1..=100).rev()));
+        assert!(generate(Preset::Sorted, 100).into_iter().eq(1..=100));
+    }
+
+    #[test]
+    fn nearly_sorted_only_moves_the_swapped_values() {
+        let list = generate(Preset::NearlySorted, 100);
+        let moved = list.iter().zip(1..).filter(|(value, place)| **value != *place).count();
+
+        assert!(is_permutation(&list));
+        assert!(moved <= 2 * InputGenerator::default().swaps);
+    }
+
+    #[test]
+    fn few_unique_has_a_handful_of_values() {
+        let mut list = generate(Preset::FewUnique, 100);
+        list.sort_unstable();
+        list.dedup();
+
+        assert_eq!(list.len(), 5);
+    }
+
+    #[test]
+    fn sawtooth_climbs_in_four_runs() {
+        let list = generate(Preset::Sawtooth, 100);
+
+        assert!(is_permutation(&list));
+        assert_eq!(descents(&list), 3);
+    }
+
+    #[test]
+    fn organ_pipe_climbs_then_falls() {
+        let list = generate(Preset::OrganPipe, 100);
+        let peak = list.iter().position(|value| *value == 100).unwrap();
+
+        assert!(is_permutation(&list));
+        assert!(list[..=peak].windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(list[peak..].windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn gaussian_bunches_up_around_the_middle() {
+        // The spread is a sixth of the length, so the middle half is one and a
+        // half spreads either side of the mean, where about 87% of the values
+        // should be
+        let list = generate(Preset::Gaussian, 1000);
+        let middle = list.iter().filter(|value| (250..=750).contains(*value)).count();
+
+        assert!(middle > 800, "{middle}");
+    }
+
+    #[test]
+    fn exponential_bunches_up_at_the_bottom() {
+        // The mean is a fifth of the length, so about 71% of the values should
+        // be in the bottom quarter
+        let list = generate(Preset::Exponential, 1000);
+        let bottom = list.iter().filter(|value| **value <= 250).count();
+
+        assert!(bottom > 600, "{bottom}");
+    }
+}
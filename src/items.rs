@@ -0,0 +1,409 @@
+use std::{cmp, fmt};
+use crate::sorting_algorithms::{
+    bead_sort::BeadSort,
+    msd_radix_sort::MsdRadixSort,
+    multikey_quicksort::MultikeyQuicksort,
+    SortingAlgorithm,
+};
+
+/// The kinds of item a list can hold.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ItemKind {
+    Unsigned,
+    Signed,
+    Float,
+    Text,
+    TextByLength,
+    Record,
+    Pixel,
+}
+
+impl ItemKind {
+    pub const ALL: [Self; 7] = [
+        Self::Unsigned,
+        Self::Signed,
+        Self::Float,
+        Self::Text,
+        Self::TextByLength,
+        Self::Record,
+        Self::Pixel,
+    ];
+
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Unsigned => "Unsigned Integers",
+            Self::Signed => "Signed Integers",
+            Self::Float => "Floats",
+            Self::Text => "Strings",
+            Self::TextByLength => "Strings by Length",
+            Self::Record => "Records by Key",
+            Self::Pixel => "Image Pixels",
+        }
+    }
+
+    /// Whether the items are integers, which can be sorted by keys made from
+    /// them.
+    pub const fn has_integers(self) -> bool {
+        matches!(self, Self::Unsigned | Self::Signed | Self::Record)
+    }
+}
+
+/// Something that can be put in a list and sorted.
+pub trait Item: Ord + Clone + fmt::Debug + fmt::Display + 'static {
+    const KIND: ItemKind;
+
+    /// Make the item that stands for a number from 1 to the length of the
+    /// list. Bigger numbers have to make items that are no smaller.
+    fn from_number(number: usize, length: usize) -> Self;
+
+    /// How tall the item's bar is. Heights have to go up with the order of
+    /// the items, since some algorithms sort by height.
+    fn height(&self) -> f32;
+
+    /// Whether the bars should be labelled with the items, for the items that
+    /// can't be told apart by their height alone.
+    const SHOW_LABELS: bool = false;
+
+    /// The item as an integer, for the kinds of item that are integers.
+    fn as_integer(&self) -> Option<i64> {
+        None
+    }
+
+    /// The color to draw the item in, for the items that have one.
+    fn color(&self) -> Option<[u8; 3]> {
+        None
+    }
+
+    /// Make the item for the pixel of an image, for the items that can be
+    /// pixels.
+    fn from_pixel(_index: usize, _color: [u8; 3]) -> Option<Self> {
+        None
+    }
+
+    /// Read an item that was typed in, or say why it can't be one.
+    fn parse(text: &str) -> Result<Self, String>;
+
+    /// Get the sorting algorithms that only work on this kind of item.
+    fn get_extra_algorithms() -> Vec<Box<dyn SortingAlgorithm<Self>>> {
+        vec![]
+    }
+}
+
+impl Item for usize {
+    const KIND: ItemKind = ItemKind::Unsigned;
+
+    fn from_number(number: usize, _length: usize) -> Self {
+        number
+    }
+
+    fn height(&self) -> f32 {
+        *self as f32
+    }
+
+    fn as_integer(&self) -> Option<i64> {
+        i64::try_from(*self).ok()
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse().map_err(|_| format!("\"{text}\" isn't a whole number of zero or more"))
+    }
+
+    // Bead sort needs to know how many beads to thread for each item
+    fn get_extra_algorithms() -> Vec<Box<dyn SortingAlgorithm<Self>>> {
+        vec![Box::new(BeadSort::default())]
+    }
+}
+
+impl Item for i64 {
+    const KIND: ItemKind = ItemKind::Signed;
+
+    // Centered around zero so that about half of them are negative
+    fn from_number(number: usize, length: usize) -> Self {
+        let signed = |number| Self::try_from(number).unwrap_or(Self::MAX);
+
+        signed(number) - signed(length.div_ceil(2))
+    }
+
+    fn height(&self) -> f32 {
+        *self as f32
+    }
+
+    fn as_integer(&self) -> Option<i64> {
+        Some(*self)
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse().map_err(|_| format!("\"{text}\" isn't a whole number"))
+    }
+}
+
+/// A float that is ordered by `f64::total_cmp`, so it can be sorted.
+#[derive(Clone, Copy, Debug)]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
+}
+
+impl Item for Float {
+    const KIND: ItemKind = ItemKind::Float;
+
+    // Centered around zero and a quarter apart
+    fn from_number(number: usize, length: usize) -> Self {
+        Self((number as f64 - f64::midpoint(length as f64, 1.0)) / 4.0)
+    }
+
+    fn height(&self) -> f32 {
+        self.0 as f32
+    }
+
+    // Infinities and NaNs have no height to draw them at
+    fn parse(text: &str) -> Result<Self, String> {
+        match text.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(Self(number)),
+            _ => Err(format!("\"{text}\" isn't a finite number")),
+        }
+    }
+}
+
+/// The built-in word list, in order. Many of the words start the same way so
+/// that the string sorts have to look further than the first character.
+const WORDS: [&str; 64] = [
+    "a", "an", "and", "ant", "any", "ape", "apple", "apt",
+    "arc", "are", "art", "as", "ash", "ask", "at", "ate",
+    "bat", "be", "bead", "beak", "bean", "bear", "bee", "beet",
+    "bet", "bit", "bite", "by", "cab", "can", "cane", "cap",
+    "car", "card", "care", "cart", "cat", "dab", "dad", "dart",
+    "data", "date", "day", "do", "dot", "ear", "earn", "east",
+    "eat", "sea", "seal", "seam", "seat", "see", "seed", "set",
+    "she", "shed", "shoe", "so", "sort", "tea", "ten", "the",
+];
+impl Item for String {
+    const KIND: ItemKind = ItemKind::Text;
+
+    // Spread out over the word list, so that short lists don't all start
+    // with the same letter
+    fn from_number(number: usize, length: usize) -> Self {
+        WORDS[(number - 1) * WORDS.len() / length].to_string()
+    }
+
+    // The first few bytes make a number that goes up in the same order the
+    // strings do
+    fn height(&self) -> f32 {
+        self.bytes()
+            .chain([0; 3])
+            .take(3)
+            .fold(0.0, |height, byte| height.mul_add(256.0, f32::from(byte)))
+    }
+
+    const SHOW_LABELS: bool = true;
+
+    fn parse(text: &str) -> Result<Self, String> {
+        Ok(text.to_string())
+    }
+
+    fn get_extra_algorithms() -> Vec<Box<dyn SortingAlgorithm<Self>>> {
+        vec![Box::new(MsdRadixSort::default()), Box::new(MultikeyQuicksort::default())]
+    }
+}
+
+/// A string that is ordered by its length alone.
+#[derive(Clone, Debug)]
+pub struct ByLength(pub String);
+
+impl PartialEq for ByLength {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+    }
+}
+
+impl Eq for ByLength {}
+
+impl PartialOrd for ByLength {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByLength {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.len().cmp(&other.0.len())
+    }
+}
+
+impl fmt::Display for ByLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Item for ByLength {
+    const KIND: ItemKind = ItemKind::TextByLength;
+
+    fn from_number(number: usize, _length: usize) -> Self {
+        Self("abcdefghijklmnopqrstuvwxyz".chars().cycle().take(number).collect())
+    }
+
+    fn height(&self) -> f32 {
+        self.0.len() as f32
+    }
+
+    const SHOW_LABELS: bool = true;
+
+    fn parse(text: &str) -> Result<Self, String> {
+        Ok(Self(text.to_string()))
+    }
+}
+
+/// A record that is ordered by its key alone, with a name that is carried
+/// along with it.
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub key: usize,
+    pub name: String,
+}
+
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Record {}
+
+impl PartialOrd for Record {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Record {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{}", self.key)
+        } else {
+            write!(f, "{} {}", self.key, self.name)
+        }
+    }
+}
+
+impl Item for Record {
+    const KIND: ItemKind = ItemKind::Record;
+
+    fn from_number(number: usize, _length: usize) -> Self {
+        const NAMES: [&str; 8] = ["Ada", "Alan", "Barbara", "Donald", "Edsger", "Grace", "Niklaus", "Tony"];
+
+        Self {
+            key: number,
+            name: NAMES[(number + NAMES.len() - 1) % NAMES.len()].to_string(),
+        }
+    }
+
+    fn height(&self) -> f32 {
+        self.key as f32
+    }
+
+    const SHOW_LABELS: bool = true;
+
+    fn as_integer(&self) -> Option<i64> {
+        i64::try_from(self.key).ok()
+    }
+
+    // Typed as the key and then the name, with a colon between them. Records
+    // without a name are just numbered.
+    fn parse(text: &str) -> Result<Self, String> {
+        let (key, name) = text.split_once(':').unwrap_or((text, ""));
+        let key = key.parse().map_err(|_| format!("\"{key}\" in \"{text}\" isn't a key of zero or more"))?;
+
+        Ok(Self { key, name: name.to_string() })
+    }
+}
+
+/// A pixel of an image, ordered by where it was in the image so that sorting
+/// the pixels puts the image back together.
+#[derive(Clone, Copy, Debug)]
+pub struct Pixel {
+    pub index: usize,
+    pub color: [u8; 3],
+}
+
+impl PartialEq for Pixel {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for Pixel {}
+
+impl PartialOrd for Pixel {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pixel {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.index.fmt(f)
+    }
+}
+
+impl Item for Pixel {
+    const KIND: ItemKind = ItemKind::Pixel;
+
+    // Pixels that aren't from an image fade from black to white
+    fn from_number(number: usize, length: usize) -> Self {
+        let shade = (number * 255 / length.max(1)) as u8;
+
+        Self { index: number - 1, color: [shade; 3] }
+    }
+
+    fn height(&self) -> f32 {
+        self.index as f32
+    }
+
+    fn color(&self) -> Option<[u8; 3]> {
+        Some(self.color)
+    }
+
+    fn from_pixel(index: usize, color: [u8; 3]) -> Option<Self> {
+        Some(Self { index, color })
+    }
+
+    fn parse(_text: &str) -> Result<Self, String> {
+        Err("Pixels can only be loaded from an image".to_string())
+    }
+}
@@ -5,6 +5,14 @@
     clippy::cast_sign_loss,
 )]
 
+mod custom_list;
+mod input_generator;
+mod items;
+mod order;
+mod pixel_image;
+mod race;
+mod renderers;
+mod shuffle_algorithms;
 mod sorting_algorithms;
 
 use std::{
@@ -13,22 +21,13 @@ use std::{
 };
 use eframe::egui::{self, epaint};
 use rand::prelude::*;
-use sorting_algorithms::SortingAlgorithm;
-
-const BAR_COLORS: [epaint::Color32; 12] = [
-    epaint::Color32::DARK_RED,
-    epaint::Color32::RED,
-    epaint::Color32::LIGHT_RED,
-    epaint::Color32::BROWN,
-    epaint::Color32::YELLOW,
-    epaint::Color32::GOLD,
-    epaint::Color32::DARK_GREEN,
-    epaint::Color32::GREEN,
-    epaint::Color32::LIGHT_GREEN,
-    epaint::Color32::DARK_BLUE,
-    epaint::Color32::BLUE,
-    epaint::Color32::LIGHT_BLUE,
-];
+use input_generator::{InputGenerator, Preset};
+use items::{ByLength, Float, Item, ItemKind, Pixel, Record};
+use order::{Key, Order};
+use pixel_image::{PixelImage, Scramble};
+use race::{Clock, CostModel, Race, Racer};
+use renderers::{tree::TreeDiagram, Renderer, Scene};
+use sorting_algorithms::{row_sort::RowSort, CallFrame, Element, Goal, Highlight, Layout, Pseudocode, SortingAlgorithm, Tree};
 
 fn main() -> eframe::Result {
     let viewport = egui::ViewportBuilder::default()
@@ -41,7 +40,7 @@ fn main() -> eframe::Result {
         ..Default::default()
     };
 
-    eframe::run_native("Sorting Algorithm Visualizer", options, Box::new(|cc| Ok(Box::new(ProgramState::new(cc)))))
+    eframe::run_native("Sorting Algorithm Visualizer", options, Box::new(|cc| Ok(Box::new(Visualizer::new(cc)))))
 }
 
 fn get_icon() -> egui::IconData {
@@ -56,47 +55,491 @@ fn get_icon() -> egui::IconData {
     }
 }
 
-struct ProgramState<T: Ord> {
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Sorting,
+    /// Running shuffles over and over from the same starting order, to see how
+    /// evenly they shuffle.
+    Shuffling,
+    /// Running several sorting algorithms side by side on the same list, to
+    /// see which finishes first.
+    Racing,
+}
+
+impl Mode {
+    /// Whether the mode sorts its lists, rather than shuffling them.
+    const fn sorts(self) -> bool {
+        !matches!(self, Self::Shuffling)
+    }
+}
+
+/// Whether the equal elements of a sorted list kept the order they started
+/// out in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stability {
+    Stable,
+    Unstable,
+    /// The algorithm made new elements out of the values rather than moving
+    /// the old ones around, so there's no order for it to have kept.
+    NotApplicable,
+}
+
+/// The most rows and columns the shuffle heatmap has. Longer lists have their
+/// positions lumped together.
+const HEATMAP_CELLS: usize = 64;
+
+/// How long a batch of shuffles gets to run each frame, so that the window
+/// keeps responding while it runs.
+const BATCH_TIME_PER_FRAME: time::Duration = time::Duration::from_millis(10);
+
+/// The program state for the kind of item being sorted. The algorithms are
+/// generic over the kind of item, so each kind needs a state of its own.
+enum Visualizer {
+    Unsigned(ProgramState<usize>),
+    Signed(ProgramState<i64>),
+    Float(ProgramState<Float>),
+    Text(ProgramState<String>),
+    TextByLength(ProgramState<ByLength>),
+    Record(ProgramState<Record>),
+    Pixel(ProgramState<Pixel>),
+}
+
+impl Visualizer {
+    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+        Self::Unsigned(ProgramState::default())
+    }
+
+    /// Update the state, and swap it out for one of another kind if that
+    /// kind was chosen.
+    fn update_state<T: Item>(state: &mut ProgramState<T>, ctx: &egui::Context, frame: &mut eframe::Frame) -> Option<Self> {
+        eframe::App::update(state, ctx, frame);
+
+        (state.kind != T::KIND).then(|| match state.kind {
+            ItemKind::Unsigned => Self::Unsigned(state.with_items()),
+            ItemKind::Signed => Self::Signed(state.with_items()),
+            ItemKind::Float => Self::Float(state.with_items()),
+            ItemKind::Text => Self::Text(state.with_items()),
+            ItemKind::TextByLength => Self::TextByLength(state.with_items()),
+            ItemKind::Record => Self::Record(state.with_items()),
+            ItemKind::Pixel => Self::Pixel(state.with_items()),
+        })
+    }
+}
+
+impl eframe::App for Visualizer {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let switched = match self {
+            Self::Unsigned(state) => Self::update_state(state, ctx, frame),
+            Self::Signed(state) => Self::update_state(state, ctx, frame),
+            Self::Float(state) => Self::update_state(state, ctx, frame),
+            Self::Text(state) => Self::update_state(state, ctx, frame),
+            Self::TextByLength(state) => Self::update_state(state, ctx, frame),
+            Self::Record(state) => Self::update_state(state, ctx, frame),
+            Self::Pixel(state) => Self::update_state(state, ctx, frame),
+        };
+
+        if let Some(visualizer) = switched {
+            *self = visualizer;
+        }
+    }
+}
+
+/// Pick a seed for a new run. They're kept short so that they're easy to read
+/// out.
+fn new_seed() -> u64 {
+    rand::random::<u32>().into()
+}
+
+struct ProgramState<T: Item> {
     // Lists
-    list: Vec<Vec<T>>,
-    sorted_list: Vec<T>,
-    highlights: Vec<(usize, usize)>,
+    list: Vec<Vec<Element<T>>>,
+    sorted_list: Vec<Element<T>>,
+    input: InputGenerator,
+    // The kind of item the lists should hold. The program state is swapped
+    // out for one that holds them when this changes.
+    kind: ItemKind,
+    highlights: Vec<((usize, usize), Highlight)>,
+    // The swaps and writes made by the last step, and the list before it
+    changes: Vec<((usize, usize), Highlight)>,
+    previous_list: Vec<Vec<Element<T>>>,
+    groups: Vec<((usize, usize), usize)>,
+    tree: Option<Tree<T>>,
+    // The calls the algorithm is in the middle of, and the one the mouse is
+    // over in the call stack panel
+    call_stack: Vec<CallFrame>,
+    hovered_frame: Option<usize>,
+    pseudocode: Option<Pseudocode>,
+    layout: Layout,
+    // Which of the renderers draws lists of bars
+    renderer: usize,
+    // The position of the character being compared in the highlighted
+    // strings
+    character: Option<usize>,
+    // The list typed in by the user, and what was wrong with the last list
+    // they gave, if anything
+    typed_list: String,
+    list_error: Option<String>,
+    // The image that image pixels come from, and how it's scrambled
+    image: PixelImage,
+    scramble: Scramble,
 
     // The algorithm and etc
-    algorithm: Option<Box<dyn SortingAlgorithm>>,
+    mode: Mode,
+    algorithm: Option<Box<dyn SortingAlgorithm<T>>>,
+    // The algorithms racing each other when racing
+    race: Race<T>,
+    goal: Goal,
+    target: usize,
     delay: time::Duration,
+    // How the list is sorted, which every element is given
+    order: Order,
+    // Everything random about a run comes from the seed, so that a run can
+    // be played again
+    seed: u64,
+
+    // How often each element has ended up in each position, for each of the
+    // shuffles run so far, on a list of the given length. The positions are
+    // lumped together into at most `HEATMAP_CELLS` rows and columns.
+    heatmap: Vec<Vec<usize>>,
+    heatmap_length: usize,
+    shuffle_runs: usize,
+    // The shuffle being run over and over without being shown, and how many
+    // more runs it has to go
+    batch: Option<(Box<dyn SortingAlgorithm<T>>, usize)>,
+    // How many times each index has been read or written during this run
+    accesses: Vec<usize>,
 
     // State bools
     running: bool,
     sorted: bool,
+    repeat_shuffles: bool,
+    // Whether the equal elements of the last sorted list kept their order, if
+    // it had any equal elements
+    stable: Option<Stability>,
 
     // Timers
     time_of_last_step: time::SystemTime,
+    // How much of the time between steps bars take to slide to where they
+    // moved to, where 0 makes them jump straight there
+    slide_time: f32,
     sorted_animation_time: f64,
 }
 
-impl ProgramState<usize> {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        Self::default()
+impl<T: Item> ProgramState<T> {
+    /// Make a program state for another kind of item, with the same settings.
+    fn with_items<U: Item>(&self) -> ProgramState<U> {
+        let mut state = ProgramState {
+            list: vec![Element::numbered(self.list.iter().flatten().count())],
+            input: self.input,
+            typed_list: self.typed_list.clone(),
+            target: self.target,
+            delay: self.delay,
+            slide_time: self.slide_time,
+            seed: self.seed,
+            image: self.image.clone(),
+            scramble: self.scramble,
+            renderer: if U::KIND == ItemKind::Pixel { renderers::get_image_renderer::<U>() } else { self.renderer },
+            // Keys made from integers only work for items that are integers,
+            // and images only go back together one way
+            order: if U::KIND == ItemKind::Pixel {
+                Order::default()
+            } else if U::KIND.has_integers() {
+                self.order
+            } else {
+                Order { key: Key::Value, ..self.order }
+            },
+            ..ProgramState::default()
+        };
+        // The racers are found again among the algorithms for the new items,
+        // if they can sort them
+        for name in self.race.names() {
+            state.add_racer(&name);
+        }
+        state.set_mode(self.mode);
+
+        state
     }
 
     fn shuffle(&mut self) {
-        let mut rng = rand::thread_rng();
+        let mut rng = StdRng::seed_from_u64(self.seed);
 
-        let mut new_list = self.list
-            .clone()
-            .into_iter()
-            .flatten()
-            .collect::<Vec<usize>>();
-        new_list.shuffle(&mut rng);
+        let length = self.list.iter().flatten().count();
+        self.list = if T::KIND == ItemKind::Pixel && self.mode.sorts() {
+            self.scrambled_image(&mut rng)
+        } else {
+            let new_list = match self.mode {
+                Mode::Sorting | Mode::Racing => self.input.generate(length, &mut rng),
+                // Shuffles always start from the same order so that their
+                // results can be compared
+                Mode::Shuffling => (1..=length).collect(),
+            };
 
-        self.list = vec![new_list];
+            vec![Element::tagged(new_list.into_iter().map(|number| T::from_number(number, length)))]
+        };
+        self.restart_algorithm();
+
+        // The new list can hold different items even when it's just as long,
+        // so it gets sorted again
+        self.sorted_list = vec![];
+        self.sorted = false;
+        self.stable = None;
+    }
+
+    /// The rows of the image scrambled, one list per row.
+    fn scrambled_image(&self, rng: &mut StdRng) -> Vec<Vec<Element<T>>> {
+        let rows = self.image.scramble(self.scramble, rng);
+
+        // The pixels are tagged all together so that no two share a tag
+        let mut elements = Element::tagged(rows.iter().flatten().filter_map(|(index, color)| T::from_pixel(*index, *color))).into_iter();
+        rows.iter().map(|row| elements.by_ref().take(row.len()).collect()).collect()
+    }
+
+    /// Use an image that the user gave, or show what was wrong with it.
+    fn use_image(&mut self, image: Result<PixelImage, String>) {
+        match image {
+            Ok(image) => {
+                self.image = image;
+                self.list_error = None;
+                self.shuffle();
+            },
+            Err(error) => self.list_error = Some(error),
+        }
+    }
+
+    /// Use a list that the user gave, or show what was wrong with it.
+    fn use_custom_list(&mut self, list: Result<Vec<T>, String>) {
+        let list = match list {
+            Ok(list) => list,
+            Err(error) => {
+                self.list_error = Some(error);
+                return;
+            },
+        };
+
+        self.list = vec![Element::tagged(list)];
+        self.list_error = None;
+        self.restart_algorithm();
+
+        self.sorted_list = vec![];
+        self.sorted = false;
+        self.stable = None;
+    }
+
+    /// Start the algorithm over on the list, with the seed and the order.
+    fn restart_algorithm(&mut self) {
+        for element in self.list.iter_mut().flatten() {
+            element.order = self.order;
+        }
+
+        // Algorithms that can't sort the list are put down, saying why
+        let list = &self.list;
+        if let Some(Err(error)) = self.algorithm.as_ref().map(|algorithm| algorithm.check_list(list)) {
+            self.algorithm = None;
+            self.running = false;
+            self.list_error = Some(error);
+        }
+        let refused = self.race.racers.iter().find_map(|racer| racer.algorithm.check_list(list).err());
+        if let Some(error) = refused {
+            self.race.racers.retain(|racer| racer.algorithm.check_list(list).is_ok());
+            self.list_error = Some(error);
+        }
 
         if let Some(algorithm) = &mut self.algorithm {
+            algorithm.set_seed(self.seed);
             algorithm.set_list(self.list.clone());
         }
+        self.race.restart(&self.list, self.seed);
+        self.accesses = vec![];
+        self.changes = vec![];
+        self.previous_list = vec![];
+    }
 
-        self.sorted = false;
+    /// Do one step of the algorithm, counting the indexes it accessed and
+    /// noting what it swapped or wrote. When racing, every racer steps.
+    fn step_algorithm(&mut self) {
+        if self.mode == Mode::Racing {
+            self.race.tick(&self.sorted_list);
+            self.time_of_last_step = time::SystemTime::now();
+            return;
+        }
+
+        let Some(algorithm) = &mut self.algorithm else {
+            return;
+        };
+
+        let before = algorithm.get_list().0;
+        algorithm.step();
+        let after = algorithm.get_list().0;
+        let accesses = algorithm.get_accesses();
+        self.time_of_last_step = time::SystemTime::now();
+
+        // The algorithm counts what it read and wrote itself, with reads
+        // going by the lists before the step and writes by the lists after it
+        let tags = |list: &[Vec<Element<T>>]| list.iter().flatten().map(|element| element.tag).collect::<Vec<_>>();
+        let (before_tags, after_tags) = (tags(&before), tags(&after));
+        if self.accesses.len() != before_tags.len() {
+            self.accesses = vec![0; before_tags.len()];
+        }
+        let offsets = |lists: &[Vec<Element<T>>]| {
+            lists
+                .iter()
+                .scan(0, |offset, list| {
+                    let start = *offset;
+                    *offset += list.len();
+                    Some(start)
+                })
+                .collect::<Vec<_>>()
+        };
+        let (before_offsets, after_offsets) = (offsets(&before), offsets(&after));
+        let reads = accesses.reads.iter().map(|(list_index, index)| before_offsets[*list_index] + index);
+        let writes = accesses.writes.iter().map(|(list_index, index)| after_offsets[*list_index] + index);
+        for index in reads.chain(writes) {
+            if let Some(count) = self.accesses.get_mut(index) {
+                *count += 1;
+            }
+        }
+        let changed = Element::changed_indexes(&before, &after);
+
+        // Two elements that traded places were swapped, and anything else
+        // that changed was written
+        let swapped = matches!(changed[..], [first, second] if before_tags[first] == after_tags[second] && before_tags[second] == after_tags[first]);
+        let highlight = if swapped { Highlight::Swap } else { Highlight::Write };
+        let positions = after
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| (0..list.len()).map(move |index| (list_index, index)))
+            .collect::<Vec<_>>();
+        self.changes = changed.into_iter().map(|index| (positions[index], highlight)).collect();
+        self.previous_list = before;
+    }
+
+    /// The list before the last step and how far the elements have got
+    /// from there, while they're still moving.
+    fn motion(&self) -> Option<(&[Vec<Element<T>>], f32)> {
+        let elapsed = time::SystemTime::now().duration_since(self.time_of_last_step).unwrap_or_default();
+        let duration = self.delay.mul_f32(self.slide_time);
+        let progress = if duration.is_zero() { 1.0 } else { elapsed.as_secs_f32() / duration.as_secs_f32() };
+
+        (progress < 1.0 && !self.previous_list.is_empty()).then_some((&self.previous_list[..], progress))
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.algorithm = None;
+        // Nothing is left of the algorithm to show
+        self.tree = None;
+        self.call_stack = vec![];
+        self.pseudocode = None;
+        self.running = false;
+        self.heatmap = vec![];
+        self.shuffle_runs = 0;
+        self.batch = None;
+        self.shuffle();
+    }
+
+    /// Add the available sorting algorithm of the name to the race, or take
+    /// it out if it's already racing. Images are sorted a row at a time.
+    fn add_racer(&mut self, name: &str) {
+        let image = T::KIND == ItemKind::Pixel;
+        let algorithm = sorting_algorithms::get_available_algorithms::<T>()
+            .into_iter()
+            .find(|algorithm| {
+                algorithm.get_name() == name && algorithm.supports_order(self.order) && (!image || algorithm.get_goal() == Goal::Sorted)
+            });
+        let Some(mut algorithm) = algorithm else {
+            return;
+        };
+
+        if image {
+            algorithm = Box::new(RowSort::new(name));
+        }
+        algorithm.set_target(self.target);
+        self.race.toggle(algorithm);
+    }
+
+    /// Run the algorithm on a list made afresh from the seed, so that the
+    /// same seed always gives the same run. Until there's a list, it's as
+    /// long as the algorithm's own. Images are sorted a row at a time.
+    fn select_algorithm(&mut self, mut algorithm: Box<dyn SortingAlgorithm<T>>) {
+        self.batch = None;
+        self.stable = None;
+        self.delay = algorithm.get_delay();
+        if T::KIND == ItemKind::Pixel && self.mode.sorts() {
+            algorithm = Box::new(RowSort::new(algorithm.get_name()));
+        } else if self.list.iter().all(Vec::is_empty) {
+            self.list = algorithm.get_list().0;
+        }
+        algorithm.set_target(self.target);
+        self.algorithm = Some(algorithm);
+        self.shuffle();
+    }
+
+    /// Count where each element of a shuffled list ended up.
+    fn record_shuffle(&mut self, list: &[Element<T>]) {
+        let length = list.len();
+        let cells = length.min(HEATMAP_CELLS);
+        if self.heatmap_length != length {
+            self.heatmap = vec![vec![0; cells]; cells];
+            self.heatmap_length = length;
+            self.shuffle_runs = 0;
+        }
+
+        for (position, element) in list.iter().enumerate() {
+            if let Some(row) = self.heatmap.get_mut(element.tag * cells / length) {
+                row[position * cells / length] += 1;
+            }
+        }
+        self.shuffle_runs += 1;
+    }
+
+    /// Start running the shuffle to the end many times over without showing
+    /// it. The runs are spread over the frames that follow.
+    fn run_shuffles(&mut self, runs: usize) {
+        let Some(algorithm) = self.algorithm.take() else {
+            return;
+        };
+
+        self.batch = Some((algorithm, runs));
+        self.running = false;
+        self.restart_batch_run();
+    }
+
+    /// Start the next run of the batch from the same order as every other
+    /// run. Each run gets the next seed, as if they had been run one by one.
+    fn restart_batch_run(&mut self) {
+        let Some((algorithm, _)) = &mut self.batch else {
+            return;
+        };
+
+        self.seed = self.seed.wrapping_add(1);
+        algorithm.set_seed(self.seed);
+        algorithm.set_list(vec![Element::numbered(self.list.iter().flatten().count())]);
+    }
+
+    /// Go on with the batch of shuffles for as long as a frame allows, and
+    /// put the shuffle back once the batch is done.
+    fn continue_batch(&mut self) {
+        let start = time::SystemTime::now();
+        while let Some((algorithm, runs)) = &mut self.batch {
+            if *runs == 0 {
+                self.algorithm = self.batch.take().map(|(algorithm, _)| algorithm);
+                self.running = false;
+                self.sorted = true;
+                return;
+            }
+            if time::SystemTime::now().duration_since(start).unwrap_or_default() > BATCH_TIME_PER_FRAME {
+                return;
+            }
+
+            if algorithm.is_finished() {
+                *runs -= 1;
+                let shuffled = algorithm.get_list().0.concat();
+                self.record_shuffle(&shuffled);
+                self.restart_batch_run();
+            } else {
+                algorithm.step();
+            }
+        }
     }
 
     fn draw_graph(&self) -> Box<dyn FnOnce(&mut egui::Ui) + '_> {
@@ -106,133 +549,332 @@ impl ProgramState<usize> {
             // x and y of the desired size of the frame is 1 times the width and 0.35
             // times the width respectively.
             let desired_size = ui.available_width() * egui::vec2(1.0, 0.35);
-            let (_, rect) = ui.allocate_space(desired_size);
+            let (_, mut rect) = ui.allocate_space(desired_size);
+
+            // Shuffles get where the elements have ended up drawn under the
+            // bars
+            if self.mode == Mode::Shuffling {
+                let heatmap_rect;
+                (rect, heatmap_rect) = rect.split_top_bottom_at_fraction(0.5);
+
+                ui.painter().extend(self.make_heatmap(heatmap_rect.shrink(10.0)));
+            }
+
+            let scene = Scene {
+                list: &self.list,
+                highlights: &self.highlights,
+                groups: &self.groups,
+                goal: self.goal,
+                character: self.character,
+                flashing_sorted: self.is_flashing_sorted(ui.ctx()),
+                motion: self.motion(),
+            };
+
+            // Algorithms that build a tree get it drawn under the bars
+            if let Some(tree) = &self.tree {
+                let tree_rect;
+                (rect, tree_rect) = rect.split_top_bottom_at_fraction(0.5);
+
+                ui.painter().extend(TreeDiagram { tree }.render(&scene, tree_rect.shrink(10.0), ui.ctx()));
+            }
 
-            let bars = self.make_bars(rect, &self.list, 10.0, 10.0, ui.ctx());
+            // Lists of bars get how often each index was accessed drawn as a
+            // strip under them
+            if self.mode == Mode::Sorting && matches!(self.layout, Layout::Bars) && !self.accesses.is_empty() {
+                let strip_rect;
+                (rect, strip_rect) = rect.split_top_bottom_at_y(rect.bottom() - 24.0);
 
-            ui.painter().extend(bars);
+                ui.painter().extend(self.make_access_strip(strip_rect.shrink2(egui::vec2(0.0, 2.0)), ui.ctx()));
+            }
+
+            // Each call gets a bracket over the bars it's on, the outermost at
+            // the top
+            if matches!(self.layout, Layout::Bars) && !self.call_stack.is_empty() {
+                let brackets_rect;
+                (brackets_rect, rect) = rect.split_top_bottom_at_y(rect.top() + (self.call_stack.len() as f32).mul_add(6.0, 6.0).min(rect.height() / 3.0));
+
+                ui.painter().extend(self.make_brackets(brackets_rect));
+            }
+
+            // Lists of bars are drawn by the chosen renderer, and the other
+            // layouts by their own
+            let renderer = renderers::get_layout_renderer(&self.layout)
+                .unwrap_or_else(|| renderers::get_available_renderers().swap_remove(self.renderer));
+            ui.painter().extend(renderer.render(&scene, rect, ui.ctx()));
         })
     }
 
-    fn make_bars(
-        &self,
-        rect: egui::Rect,
-        list: &[Vec<usize>],
-        base_height: f32,
-        base_spacing: f32,
-        ctx: &egui::Context,
-    ) -> Vec<epaint::Shape> {
-        let mut bars = vec![];
-        let max_height = rect.height() - base_height - 25.0;
-
-        let filled_in = list.iter()
-            .map(|l| vec![true; l.len()])
-            .collect::<Vec<Vec<bool>>>()
-            .join(&[false][..]);
-        let bar_width = rect.width() / filled_in.len() as f32;
-        let mut color_index = 0;
-        for slot in filled_in.iter().enumerate() {
-            if *slot.1 {
-                let color = if list.len() == 1 {
-                    epaint::Color32::DARK_GRAY
-                } else {
-                    BAR_COLORS[color_index % BAR_COLORS.len()]
-                };
+    /// Draw each racer in a cell of its own, laid out in a grid about as many
+    /// cells across as down.
+    fn draw_race(&self) -> Box<dyn FnOnce(&mut egui::Ui) + '_> {
+        Box::new(move |ui| {
+            ui.ctx().request_repaint();
+
+            let racers = &self.race.racers;
+            if racers.len() < race::MIN_RACERS {
+                ui.label(format!("Pick {} to {} algorithms to race", race::MIN_RACERS, race::MAX_RACERS));
+                return;
+            }
 
-                let base = epaint::Shape::rect_filled(epaint::Rect::from_two_pos(
-                    epaint::pos2((slot.0 as f32).mul_add(bar_width, rect.left()), rect.bottom()),
-                    epaint::pos2(((slot.0 + 1) as f32).mul_add(bar_width, rect.left()), rect.bottom() - base_height),
-                ), 0.0, color);
+            let (_, rect) = ui.allocate_space(ui.available_size());
+            let columns = racers.len().isqrt() + usize::from(racers.len().isqrt().pow(2) < racers.len());
+            let rows = racers.len().div_ceil(columns);
+            let cell_size = rect.size() / egui::vec2(columns as f32, rows as f32);
+            let renderers = renderers::get_available_renderers();
 
-                bars.push(base);
-            } else {
-                color_index += 1;
+            for (index, racer) in racers.iter().enumerate() {
+                let corner = rect.min + cell_size * egui::vec2((index % columns) as f32, (index / columns) as f32);
+                let cell = egui::Rect::from_min_size(corner, cell_size).shrink(6.0);
+                let (title_rect, bars_rect) = cell.split_top_bottom_at_y(cell.top() + 22.0);
+
+                // Racers that have finished get framed in the color of sorted
+                // elements, with their place
+                let place = self.race.place(index);
+                let frame_color = if place.is_some() { Highlight::Final.color() } else { epaint::Color32::DARK_GRAY };
+                ui.painter().rect_stroke(cell, 4.0, epaint::Stroke::new(1.0, frame_color));
+                let progress = racer_progress(&self.race, racer);
+                let status = place.map_or_else(|| progress.clone(), |place| format!("#{place} in {progress}"));
+                let text_color = ui.visuals().strong_text_color();
+                for (align, text) in [(egui::Align2::LEFT_CENTER, racer.algorithm.get_name()), (egui::Align2::RIGHT_CENTER, &status)] {
+                    let position = align.pos_in_rect(&title_rect.shrink2(egui::vec2(6.0, 0.0)));
+                    ui.painter().text(position, align, text, egui::FontId::proportional(14.0), text_color);
+                }
+
+                let (list, compares) = racer.algorithm.get_list();
+                let mut highlights = racer.algorithm.get_marks();
+                if self.running {
+                    highlights.extend(compares.into_iter().map(|position| (position, Highlight::Compare)));
+                }
+                let scene = Scene {
+                    list: &list,
+                    highlights: &highlights,
+                    groups: &racer.algorithm.get_groups(),
+                    goal: racer.algorithm.get_goal(),
+                    character: racer.algorithm.get_character().filter(|_| self.running),
+                    flashing_sorted: false,
+                    motion: None,
+                };
+                ui.painter().extend(renderers[self.renderer].render(&scene, bars_rect.shrink(6.0), ui.ctx()));
             }
+        })
+    }
+
+    fn make_heatmap(&self, rect: egui::Rect) -> Vec<epaint::Shape> {
+        const UNIFORM: [f32; 3] = [40.0, 40.0, 40.0];
+        const OFTEN: [f32; 3] = [255.0, 80.0, 80.0];
+        const RARELY: [f32; 3] = [80.0, 120.0, 255.0];
+
+        let cells = self.heatmap.len();
+        if cells == 0 || self.shuffle_runs == 0 {
+            return vec![];
         }
 
-        if list.is_empty() {
-            return bars;
+        let cell_size = egui::vec2(rect.width() / cells as f32, rect.height() / cells as f32);
+        let expected = self.shuffle_runs as f32 * self.heatmap_length as f32 / (cells * cells) as f32;
+        let mut shapes = vec![];
+        for (start, row) in self.heatmap.iter().enumerate() {
+            for (end, count) in row.iter().enumerate() {
+                // How many times more (or less) often this happened than it
+                // would with a perfectly even shuffle
+                let bias = (*count as f32 / expected - 1.0).clamp(-1.0, 1.0);
+                let target = if bias > 0.0 { OFTEN } else { RARELY };
+                let [r, g, b] = [0, 1, 2].map(|i| (target[i] - UNIFORM[i]).mul_add(bias.abs(), UNIFORM[i]) as u8);
+
+                shapes.push(epaint::Shape::rect_filled(
+                    egui::Rect::from_min_size(rect.left_top() + cell_size * egui::vec2(end as f32, start as f32), cell_size),
+                    epaint::Rounding::ZERO,
+                    epaint::Color32::from_rgb(r, g, b),
+                ));
+            }
         }
 
-        let max_value = *list.iter().flatten().max().unwrap_or(&0) as f32;
-        let min_value = list.iter().flatten().min().unwrap_or(&0).saturating_sub(1) as f32;
-        let list_directory = list
-            .iter()
-            .map(|v| 0..v.len())
-            .scan(0, |len, list| {
-                let new_list = list
-                    .clone()
-                    .map(|i| i + *len)
-                    .collect::<Vec<_>>();
-                *len += list.len() + 1; // Add one to account for the 0 values
-                                        // in between
-                Some(new_list)
-            })
-            .collect::<Vec<_>>();
-        let highlight_indices = self.highlights
-            .iter()
-            // If there is an error here you probably set your highlights up
-            // wrong
-            .map(|(first_index, second_index)| list_directory[*first_index][*second_index])
-            .collect::<Vec<_>>();
+        shapes
+    }
 
-        for number in list.join(&[min_value as usize][..]).into_iter().enumerate() {
-            let bar_height = ((number.1 as f32 - min_value) / max_value) * max_height;
-            let color = if ctx.input(|i| i.time) - self.sorted_animation_time < 0.25 {
-                epaint::Color32::LIGHT_GREEN
-            } else if highlight_indices.contains(&number.0) {
-                epaint::Color32::LIGHT_RED
-            } else {
-                epaint::Color32::WHITE
+    fn make_brackets(&self, rect: egui::Rect) -> Vec<epaint::Shape> {
+        let mut shapes = vec![];
+
+        // The brackets line up with the bars, leaving the same gaps between
+        // the lists
+        let mut slots = vec![];
+        let mut slot = 0_usize;
+        for list in &self.list {
+            for _ in list {
+                slots.push(slot);
+                slot += 1;
+            }
+            slot += 1;
+        }
+        let slot_width = rect.width() / slot.saturating_sub(1).max(1) as f32;
+        let spacing = (rect.height() - 4.0) / self.call_stack.len().max(1) as f32;
+
+        for (depth, frame) in self.call_stack.iter().enumerate() {
+            let (Some(first), Some(last)) = (slots.get(frame.range.start), frame.range.end.checked_sub(1).and_then(|last| slots.get(last))) else {
+                continue;
             };
 
-            let bar = epaint::Shape::rect_filled(
-                egui::Rect::from_two_pos(
-                    epaint::pos2(bar_width.mul_add(number.0 as f32, rect.left()), rect.bottom() - base_height - base_spacing),
-                    epaint::pos2(bar_width.mul_add((number.0 + 1) as f32, rect.left()), rect.bottom() - base_height - base_spacing - bar_height),
-                ),
-                epaint::Rounding::ZERO,
-                color,
-            );
-            bars.push(bar);
+            let hovered = self.hovered_frame == Some(depth);
+            let stroke = epaint::Stroke::new(if hovered { 3.0 } else { 1.5 }, frame_color(depth, hovered));
+            let y = (depth as f32).mul_add(spacing, rect.top() + 2.0);
+            let left = (*first as f32).mul_add(slot_width, rect.left()) + 1.0;
+            let right = ((*last + 1) as f32).mul_add(slot_width, rect.left()) - 1.0;
+            shapes.push(epaint::Shape::line(
+                vec![epaint::pos2(left, rect.bottom()), epaint::pos2(left, y), epaint::pos2(right, y), epaint::pos2(right, rect.bottom())],
+                stroke,
+            ));
         }
 
-        bars
+        shapes
+    }
+
+    fn make_access_strip(&self, rect: egui::Rect, ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let mut shapes = vec![];
+        let most = self.accesses.iter().copied().max().unwrap_or(0).max(1);
+
+        // The strip lines up with the bars, leaving the same gaps between the
+        // lists
+        let slots = self.list.iter().map(Vec::len).sum::<usize>() + self.list.len().saturating_sub(1);
+        let slot_width = rect.width() / slots.max(1) as f32;
+        let mut slot = 0;
+        let mut counts = self.accesses.iter();
+        for list in &self.list {
+            for count in counts.by_ref().take(list.len()) {
+                // Indexes that are never touched stay black, and the most
+                // used ones are yellow
+                let heat = *count as f32 / most as f32;
+                let color: epaint::Color32 = epaint::Hsva::new(heat * 0.16, 1.0, heat.sqrt(), 1.0).into();
+                shapes.push(epaint::Shape::rect_filled(
+                    egui::Rect::from_x_y_ranges(
+                        (slot as f32).mul_add(slot_width, rect.left())..=((slot + 1) as f32).mul_add(slot_width, rect.left()),
+                        rect.y_range(),
+                    ),
+                    epaint::Rounding::ZERO,
+                    color,
+                ));
+                slot += 1;
+            }
+            slot += 1;
+        }
+
+        shapes.push(ctx.fonts(|fonts| epaint::Shape::text(
+            fonts,
+            rect.right_center() - egui::vec2(4.0, 0.0),
+            egui::Align2::RIGHT_CENTER,
+            format!("Most accesses: {most}"),
+            egui::FontId::proportional(11.0),
+            epaint::Color32::GRAY,
+        )));
+
+        shapes
+    }
+
+    /// Whether the list was sorted recently enough to still be flashing green.
+    fn is_flashing_sorted(&self, ctx: &egui::Context) -> bool {
+        ctx.input(|i| i.time) - self.sorted_animation_time < 0.25
     }
 }
 
-impl Default for ProgramState<usize> {
+impl<T: Item> Default for ProgramState<T> {
     fn default() -> Self {
         Self {
             list: vec![],
             sorted_list: vec![],
+            input: InputGenerator::default(),
+            kind: T::KIND,
             highlights: vec![],
+            changes: vec![],
+            previous_list: vec![],
+            groups: vec![],
+            tree: None,
+            call_stack: vec![],
+            hovered_frame: None,
+            pseudocode: None,
+            layout: Layout::Bars,
+            renderer: 0,
+            character: None,
+            typed_list: String::new(),
+            list_error: None,
+            image: PixelImage::builtin(),
+            scramble: Scramble::Columns,
 
+            mode: Mode::Sorting,
             algorithm: None,
+            race: Race::default(),
+            goal: Goal::Sorted,
+            // The middle of the algorithms' default lists
+            target: 7,
             delay: time::Duration::from_millis(100),
+            order: Order::default(),
+            seed: new_seed(),
+
+            heatmap: vec![],
+            heatmap_length: 0,
+            shuffle_runs: 0,
+            batch: None,
+            accesses: vec![],
 
             running: false,
             sorted: false,
+            repeat_shuffles: true,
+            stable: None,
 
             time_of_last_step: time::UNIX_EPOCH,
+            slide_time: 0.7,
             sorted_animation_time: -1000.0,
         }
     }
 }
 
-impl eframe::App for ProgramState<usize> {
+impl<T: Item> eframe::App for ProgramState<T> {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         draw_algorithm_selection(self, ctx);
 
+        draw_pseudocode(self, ctx);
+
         draw_settings_panel(self, ctx);
 
+        draw_call_stack(self, ctx);
+
+        if self.mode == Mode::Racing {
+            draw_leaderboard(self, ctx);
+        }
+
+        // Lists dropped onto the window are used like typed ones, and so are
+        // images when sorting pixels
+        let dropped = ctx.input(|input| input.raw.dropped_files.first().cloned());
+        if let (Some(file), true) = (dropped, self.mode.sorts()) {
+            if T::KIND == ItemKind::Pixel {
+                if let Some(path) = &file.path {
+                    self.use_image(PixelImage::open(path));
+                }
+            } else {
+                self.use_custom_list(custom_list::read_dropped_file(&file));
+            }
+        }
+
         // Use the rest of the space in the middle to show the actual graph
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered_justified(|ui| {
                 ui.add_space(25.0);
                 ui.heading("Sorting Algorithm Visualizer");
+
+                if self.mode.sorts() {
+                    draw_legend(ui);
+                }
+
+                if let Some(stable) = self.stable {
+                    ui.label(match stable {
+                        Stability::Stable => "Stable: the equal elements kept their order",
+                        Stability::Unstable => "Unstable: the equal elements changed order",
+                        Stability::NotApplicable => "Stability doesn't apply: the algorithm rebuilt the values",
+                    });
+                }
             });
-            ui.centered_and_justified(self.draw_graph());
+            if self.mode == Mode::Racing {
+                ui.centered_and_justified(self.draw_race());
+            } else {
+                ui.centered_and_justified(self.draw_graph());
+            }
         });
 
         // Things that need to be updated every frame (e.g. checking if the list
@@ -244,19 +886,212 @@ impl eframe::App for ProgramState<usize> {
     }
 }
 
+/// The color of the call at the depth, in the call stack panel and in its
+/// bracket over the bars.
+fn frame_color(depth: usize, hovered: bool) -> epaint::Color32 {
+    if hovered {
+        epaint::Color32::WHITE
+    } else {
+        epaint::Hsva::new((depth as f32 * 0.13).fract(), 0.6, 0.9, 1.0).into()
+    }
+}
+
+// Draw the calls the algorithm is in the middle of, for the algorithms that
+// recurse
+fn draw_call_stack<T: Item>(state: &mut ProgramState<T>, ctx: &egui::Context) {
+    state.hovered_frame = None;
+    if state.call_stack.is_empty() {
+        return;
+    }
+
+    egui::TopBottomPanel::bottom(egui::Id::new("call stack panel")).resizable(false).exact_height(120.0).show(ctx, |ui| {
+        ui.add_space(4.0);
+        ui.strong("Call Stack");
+        egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+            for (depth, frame) in state.call_stack.iter().enumerate() {
+                let response = ui.horizontal(|ui| {
+                    ui.add_space(depth as f32 * 12.0);
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 2.0, frame_color(depth, false));
+                    ui.label(format!("Depth {depth}: {} to {}", frame.range.start, frame.range.end.saturating_sub(1)));
+                    ui.weak(frame.phase.get_name());
+                }).response;
+
+                if ui.rect_contains_pointer(response.rect) {
+                    state.hovered_frame = Some(depth);
+                }
+            }
+        });
+    });
+}
+
+// Draw the algorithm's pseudocode with the line it's on highlighted, and its
+// variables under it
+fn draw_pseudocode<T: Item>(state: &ProgramState<T>, ctx: &egui::Context) {
+    let Some(pseudocode) = &state.pseudocode else {
+        return;
+    };
+
+    egui::SidePanel::right(egui::Id::new("pseudocode panel")).resizable(false).show(ctx, |ui| {
+        ui.add_space(8.0);
+        ui.strong("Pseudocode");
+        ui.add_space(4.0);
+        for (index, line) in pseudocode.lines.iter().enumerate() {
+            let text = egui::RichText::new(format!("{:>2} {line}", index + 1)).monospace();
+            if pseudocode.line == Some(index) {
+                ui.label(text.background_color(ui.visuals().selection.bg_fill).color(ui.visuals().strong_text_color()));
+            } else {
+                ui.label(text);
+            }
+        }
+
+        ui.add_space(10.0);
+        ui.strong("Variables");
+        egui::Grid::new("pseudocode variables").num_columns(2).show(ui, |ui| {
+            for (name, value) in &pseudocode.variables {
+                ui.monospace(*name);
+                ui.monospace(value);
+                ui.end_row();
+            }
+        });
+    });
+}
+
+// Draw the racers that have finished in the order they finished, and the
+// ones still going
+fn draw_leaderboard<T: Item>(state: &ProgramState<T>, ctx: &egui::Context) {
+    egui::TopBottomPanel::bottom(egui::Id::new("leaderboard panel")).resizable(false).exact_height(120.0).show(ctx, |ui| {
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.strong("Finish Order");
+            ui.weak(format!("Tick {}", state.race.ticks));
+        });
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("leaderboard").num_columns(4).show(ui, |ui| {
+                let unfinished = (0..state.race.racers.len()).filter(|index| state.race.racers[*index].finish.is_none());
+                for index in state.race.finish_order.iter().copied().chain(unfinished) {
+                    let racer = &state.race.racers[index];
+                    match state.race.place(index) {
+                        Some(place) => ui.label(format!("#{place}")),
+                        None => ui.weak("-"),
+                    };
+                    ui.label(racer.algorithm.get_name());
+                    ui.label(racer_progress(&state.race, racer));
+                    ui.weak(format!("{} comparisons, {} writes", racer.comparisons, racer.writes));
+                    ui.end_row();
+                }
+            });
+        });
+    });
+}
+
+/// How far the racer has got, in what the race's clock counts.
+fn racer_progress<T: Item>(race: &Race<T>, racer: &Racer<T>) -> String {
+    match race.clock {
+        Clock::Steps => format!("{} steps", racer.steps),
+        Clock::Operations(_) => format!("{:.0} operations", racer.spent),
+    }
+}
+
+// Draw the choice of what the race's clock hands out to each racer, and what
+// each operation costs when it hands out operations
+fn draw_race_settings<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    let mut clock = state.race.clock;
+    ui.horizontal(|ui| {
+        ui.label("Race clock:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            egui::ComboBox::from_id_source("race clock")
+                .selected_text(clock.get_name())
+                .show_ui(ui, |ui| {
+                    for option in [Clock::Steps, Clock::Operations(CostModel::default())] {
+                        let selected = clock.get_name() == option.get_name();
+                        if ui.selectable_label(selected, option.get_name()).clicked() && !selected {
+                            clock = option;
+                        }
+                    }
+                });
+        });
+    });
+    if let Clock::Operations(cost_model) = &mut clock {
+        let settings = [
+            ("Operations per tick:", &mut cost_model.per_tick, 1.0..=1000.0),
+            ("Comparison cost:", &mut cost_model.comparison, 0.1..=100.0),
+            ("Write cost:", &mut cost_model.write, 0.1..=100.0),
+        ];
+        for (label, value, range) in settings {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                    ui.add(egui::DragValue::new(value).speed(0.05).range(range));
+                });
+            });
+        }
+    }
+
+    // The race starts over so that every racer is timed by the same clock
+    if clock != state.race.clock {
+        state.race.clock = clock;
+        state.running = false;
+        state.sorted = false;
+        state.restart_algorithm();
+    }
+}
+
+// Draw what each color of highlight means
+fn draw_legend(ui: &mut egui::Ui) {
+    ui.horizontal_wrapped(|ui| {
+        for highlight in Highlight::ALL {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, highlight.color());
+            ui.label(highlight.get_name());
+            ui.add_space(6.0);
+        }
+    });
+}
+
 // Draw right panel
-fn draw_algorithm_selection(state: &mut ProgramState<usize>, ctx: &egui::Context) {
+fn draw_algorithm_selection<T: Item>(state: &mut ProgramState<T>, ctx: &egui::Context) {
     egui::SidePanel::right(egui::Id::new("algorithm selection panel")).resizable(false).show(ctx, |ui| {
         ui.vertical_centered_justified(|ui| {
             ui.add_space(8.0);
             ui.heading("Choose the Agorithm");
             ui.add_space(15.0);
 
-            for algorithm in sorting_algorithms::get_available_algorithms() {
+            ui.horizontal(|ui| {
+                for (mode, name) in [(Mode::Sorting, "Sorting"), (Mode::Shuffling, "Shuffling"), (Mode::Racing, "Racing")] {
+                    if ui.selectable_label(state.mode == mode, name).clicked() && state.mode != mode {
+                        state.set_mode(mode);
+                    }
+                }
+            });
+            ui.add_space(10.0);
+
+            let algorithms = match state.mode {
+                Mode::Sorting | Mode::Racing => sorting_algorithms::get_available_algorithms(),
+                Mode::Shuffling => shuffle_algorithms::get_available_shuffles(),
+            };
+            // Images are sorted a row at a time, which only makes sense for
+            // the algorithms that sort the whole list
+            let order = state.order;
+            let image = T::KIND == ItemKind::Pixel && state.mode.sorts();
+            let algorithms = algorithms
+                .into_iter()
+                .filter(|algorithm| algorithm.supports_order(order) && (!image || algorithm.get_goal() == Goal::Sorted));
+            for algorithm in algorithms {
+                // Racers are picked and unpicked, and the race starts over
+                if state.mode == Mode::Racing {
+                    let name = algorithm.get_name();
+                    if ui.selectable_label(state.race.contains(name), name).clicked() {
+                        state.add_racer(name);
+                        state.running = false;
+                        state.sorted = false;
+                        state.restart_algorithm();
+                    }
+                    continue;
+                }
+
                 if ui.button(algorithm.get_name()).clicked() {
-                    state.list = algorithm.get_list().0.into_iter().collect();
-                    state.delay = algorithm.get_delay();
-                    state.algorithm = Some(algorithm);
+                    state.select_algorithm(algorithm);
                 }
             }
         });
@@ -264,7 +1099,7 @@ fn draw_algorithm_selection(state: &mut ProgramState<usize>, ctx: &egui::Context
 }
 
 // Draw the left panel
-fn draw_settings_panel(state: &mut ProgramState<usize>, ctx: &egui::Context) {
+fn draw_settings_panel<T: Item>(state: &mut ProgramState<T>, ctx: &egui::Context) {
     egui::SidePanel::left(egui::Id::new("settings panel")).resizable(false).show(ctx, |ui| {
         ui.vertical_centered_justified(|ui| {
             ui.add_space(8.0);
@@ -279,16 +1114,19 @@ fn draw_settings_panel(state: &mut ProgramState<usize>, ctx: &egui::Context) {
                 }
                 if ui.add(egui::Button::new("Step").min_size(button_size)).clicked() && !state.sorted {
                     state.running = false;
-
-                    if let Some(ref mut algorithm) = &mut state.algorithm {
-                        algorithm.step();
-                    }
+                    state.step_algorithm();
                 }
                 if ui.add(egui::Button::new("Pause").min_size(button_size)).clicked() && !state.sorted {
                     state.running = false;
                 }
             });
-            if ui.button("Shuffle").clicked() {
+            let shuffle_text = match state.mode {
+                Mode::Sorting | Mode::Racing => "Shuffle",
+                Mode::Shuffling => "Reset",
+            };
+            // The list is made from the seed, so shuffling it again only gives
+            // another list once there's a new seed
+            if ui.button(shuffle_text).clicked() {
                 state.shuffle();
             }
 
@@ -308,62 +1146,440 @@ fn draw_settings_panel(state: &mut ProgramState<usize>, ctx: &egui::Context) {
             // Sliders
             ui.horizontal(|ui| {
                 let mut length = state.list.iter().flatten().count();
+                // Images are as long as they have pixels
+                let image = T::KIND == ItemKind::Pixel && state.mode.sorts();
                 ui.label("List length: ");
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
-                    ui.add(egui::DragValue::new(&mut length).speed(0.05));
+                    ui.add_enabled(!image, egui::DragValue::new(&mut length).speed(0.05));
                 });
 
                 if state.list.iter().flatten().count() != length {
-                    state.list = vec![(1..=length).collect()];
-
-                    if let Some(algorithm) = &mut state.algorithm {
-                        algorithm.set_list(state.list.clone());
-                    }
+                    state.list = vec![Element::numbered(length)];
+                    state.restart_algorithm();
                 }
             });
-            ui.horizontal(|ui| {
-                let mut delay = state.delay.as_millis() as u64;
-                ui.label("Time between steps:");
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
-                    ui.add(egui::DragValue::new(&mut delay).speed(0.25));
+            draw_seed_setting(state, ui);
+            draw_view_setting(state, ui);
+            if state.mode.sorts() {
+                draw_input_settings(state, ui);
+                if T::KIND != ItemKind::Pixel {
+                    draw_order_settings(state, ui);
+                }
+            }
+            draw_timing_settings(state, ui);
+
+            let racing_to_target = state.race.racers.iter().any(|racer| racer.algorithm.get_goal() != Goal::Sorted);
+            if state.goal != Goal::Sorted || (state.mode == Mode::Racing && racing_to_target) {
+                ui.horizontal(|ui| {
+                    let mut target = state.target;
+                    let last_index = state.list.iter().flatten().count().saturating_sub(1);
+                    ui.label("Target index:");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                        ui.add(egui::DragValue::new(&mut target).speed(0.05).range(0..=last_index));
+                    });
+
+                    if target != state.target {
+                        state.target = target;
+                        state.sorted = false;
+
+                        if let Some(algorithm) = &mut state.algorithm {
+                            algorithm.set_target(target);
+                        }
+                        state.race.set_target(target);
+                        if state.mode == Mode::Racing {
+                            state.restart_algorithm();
+                        }
+                    }
+                });
+            }
+
+            if let Some(algorithm) = &mut state.algorithm {
+                algorithm.draw_settings(ui);
+            }
+
+            if state.mode == Mode::Shuffling {
+                draw_shuffle_settings(state, ui);
+            }
+            if state.mode == Mode::Racing {
+                draw_race_settings(state, ui);
+            }
+        });
+    });
+}
+
+// Draw the settings for how fast the algorithm goes
+fn draw_timing_settings<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        let mut delay = state.delay.as_millis() as u64;
+        ui.label("Time between steps:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            ui.add(egui::DragValue::new(&mut delay).speed(0.25));
+        });
+
+        if delay != state.delay.as_millis() as u64 {
+            state.delay = time::Duration::from_millis(delay);
+            state.restart_algorithm();
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Time spent sliding:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            ui.add(egui::DragValue::new(&mut state.slide_time)
+                .speed(0.01)
+                .range(0.0..=1.0)
+                .custom_formatter(|fraction, _| format!("{:.0}%", fraction * 100.0)));
+        });
+    });
+}
+
+// Draw the choice of how lists of bars are drawn
+fn draw_view_setting<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    let renderers = renderers::get_available_renderers::<T>();
+    ui.horizontal(|ui| {
+        ui.label("View:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            egui::ComboBox::from_id_source("view")
+                .selected_text(renderers[state.renderer].get_name())
+                .show_ui(ui, |ui| {
+                    for (index, renderer) in renderers.iter().enumerate() {
+                        ui.selectable_value(&mut state.renderer, index, renderer.get_name());
+                    }
                 });
+        });
+    });
+}
 
-                if delay != state.delay.as_millis() as u64 {
-                    state.delay = time::Duration::from_millis(delay);
+// Draw the seed that the lists and algorithms get their random numbers from
+fn draw_seed_setting<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        let mut seed = state.seed;
+        ui.label("Seed:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            if ui.button("New seed").clicked() {
+                seed = new_seed();
+            }
+            ui.add(egui::DragValue::new(&mut seed).speed(0.05));
+        });
 
-                    if let Some(algorithm) = &mut state.algorithm {
-                        algorithm.set_list(state.list.clone());
+        if seed != state.seed {
+            state.seed = seed;
+            state.shuffle();
+        }
+    });
+}
+
+// Draw the settings for how lists are made
+fn draw_input_settings<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.label("Items:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            egui::ComboBox::from_id_source("item kind")
+                .selected_text(state.kind.get_name())
+                .show_ui(ui, |ui| {
+                    for kind in ItemKind::ALL {
+                        ui.selectable_value(&mut state.kind, kind, kind.get_name());
                     }
-                }
+                });
+        });
+    });
+
+    if T::KIND == ItemKind::Pixel {
+        draw_image_settings(state, ui);
+        return;
+    }
+
+    let mut preset = state.input.preset;
+    let mut swaps = state.input.swaps;
+    ui.horizontal(|ui| {
+        ui.label("Input:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            egui::ComboBox::from_id_source("input preset")
+                .selected_text(preset.get_name())
+                .show_ui(ui, |ui| {
+                    for option in Preset::ALL {
+                        ui.selectable_value(&mut preset, option, option.get_name());
+                    }
+                });
+        });
+    });
+    if preset == Preset::NearlySorted {
+        ui.horizontal(|ui| {
+            ui.label("Swaps:");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                ui.add(egui::DragValue::new(&mut swaps).speed(0.05));
+            });
+        });
+    }
+
+    if preset != state.input.preset || swaps != state.input.swaps {
+        state.input.preset = preset;
+        state.input.swaps = swaps;
+        state.shuffle();
+    }
+
+    draw_custom_list(state, ui);
+}
+
+// Draw the settings for how the image is scrambled, and the button for loading
+// one
+fn draw_image_settings<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    let mut scramble = state.scramble;
+    ui.horizontal(|ui| {
+        ui.label("Scramble:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            egui::ComboBox::from_id_source("image scramble")
+                .selected_text(scramble.get_name())
+                .show_ui(ui, |ui| {
+                    for option in Scramble::ALL {
+                        ui.selectable_value(&mut scramble, option, option.get_name());
+                    }
+                });
+        });
+    });
+    if scramble != state.scramble {
+        state.scramble = scramble;
+        state.shuffle();
+    }
+
+    if ui.button("Load image...").clicked() {
+        let path = rfd::FileDialog::new()
+            .add_filter("Images", &["png"])
+            .pick_file();
+        if let Some(path) = path {
+            state.use_image(PixelImage::open(&path));
+        }
+    }
+    if let Some(error) = &state.list_error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+    }
+}
+
+// Draw the box for typing in a list, and the button for loading one
+fn draw_custom_list<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    ui.label("Custom list:");
+    ui.add(egui::TextEdit::multiline(&mut state.typed_list)
+        .hint_text("Separated by spaces or commas")
+        .desired_rows(2));
+
+    let mut result = None;
+    ui.horizontal(|ui| {
+        if ui.button("Use this list").clicked() {
+            result = Some(custom_list::parse_list(&state.typed_list));
+        }
+        if ui.button("Load file...").clicked() {
+            let path = rfd::FileDialog::new()
+                .add_filter("Lists", &["txt", "csv", "json"])
+                .pick_file();
+            result = path.map(|path| custom_list::read_file(&path));
+        }
+    });
+    ui.label("Lists can also be dropped onto the window.");
+
+    if let Some(result) = result {
+        state.use_custom_list(result);
+    }
+    if let Some(error) = &state.list_error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+    }
+}
+
+// Draw the settings for which way the list is sorted and what by
+fn draw_order_settings<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    let mut order = state.order;
+    ui.horizontal(|ui| {
+        ui.label("Order:");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            egui::ComboBox::from_id_source("order direction")
+                .selected_text(if order.descending { "Descending" } else { "Ascending" })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut order.descending, false, "Ascending");
+                    ui.selectable_value(&mut order.descending, true, "Descending");
+                });
+        });
+    });
+    if T::KIND.has_integers() {
+        ui.horizontal(|ui| {
+            ui.label("Key:");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                egui::ComboBox::from_id_source("order key")
+                    .selected_text(order.key.get_name())
+                    .show_ui(ui, |ui| {
+                        for key in Key::ALL {
+                            if ui.selectable_label(order.key.is_same_kind(key), key.get_name()).clicked() && !order.key.is_same_kind(key) {
+                                order.key = key;
+                            }
+                        }
+                    });
             });
         });
+    }
+    if let Key::Modulo(k) = &mut order.key {
+        ui.horizontal(|ui| {
+            ui.label("k:");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                ui.add(egui::DragValue::new(k).speed(0.05).range(1..=u64::MAX));
+            });
+        });
+    }
+
+    if order != state.order {
+        state.order = order;
+
+        // Some algorithms can't sort by every key
+        if state.algorithm.as_ref().is_some_and(|algorithm| !algorithm.supports_order(order)) {
+            state.algorithm = None;
+            state.running = false;
+        }
+        state.race.racers.retain(|racer| racer.algorithm.supports_order(order));
+        state.restart_algorithm();
+        state.sorted_list = vec![];
+        state.sorted = false;
+        state.stable = None;
+    }
+}
+
+// Draw the settings for running shuffles over and over
+fn draw_shuffle_settings<T: Item>(state: &mut ProgramState<T>, ui: &mut egui::Ui) {
+    ui.checkbox(&mut state.repeat_shuffles, "Keep shuffling after each run");
+    ui.horizontal(|ui| {
+        match &state.batch {
+            Some((_, runs)) => ui.label(format!("Runs: {} ({runs} to go)", state.shuffle_runs)),
+            None => ui.label(format!("Runs: {}", state.shuffle_runs)),
+        };
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+            if ui.button("Clear").clicked() {
+                state.heatmap = vec![];
+                state.heatmap_length = 0;
+                state.shuffle_runs = 0;
+                if let Some((_, runs)) = &mut state.batch {
+                    *runs = 0;
+                }
+            }
+            if ui.add_enabled(state.batch.is_none(), egui::Button::new("Run 1000")).clicked() {
+                state.run_shuffles(1000);
+            }
+        });
     });
 }
 
 // Updating logic
-fn frame_update(state: &mut ProgramState<usize>, ctx: &egui::Context) {
+fn frame_update<T: Item>(state: &mut ProgramState<T>, ctx: &egui::Context) {
+    if state.mode == Mode::Racing {
+        race_update(state, ctx);
+        return;
+    }
+    if state.batch.is_some() {
+        state.continue_batch();
+        return;
+    }
+
     if let Some(algorithm) = &state.algorithm {
         state.list = algorithm.get_list().0.into_iter().collect();
-        state.highlights = if state.running {
-            algorithm.get_list().1
+        state.highlights = algorithm.get_marks();
+        if state.running {
+            state.highlights.extend(algorithm.get_list().1.into_iter().map(|position| (position, Highlight::Compare)));
+            state.highlights.extend(state.changes.iter().copied());
+        }
+        state.groups = algorithm.get_groups();
+        state.tree = algorithm.get_tree();
+        state.call_stack = algorithm.get_call_stack();
+        state.pseudocode = algorithm.get_pseudocode();
+        state.character = if state.running {
+            algorithm.get_character()
         } else {
-            vec![]
+            None
         };
+        state.layout = algorithm.get_layout();
+        state.goal = algorithm.get_goal();
     }
-    let mut flat_list = state.list.clone().into_iter().flatten().collect::<Vec<usize>>();
-    if !state.sorted && flat_list == state.sorted_list {
-        state.sorted = true;
-        state.running = false;
-        state.sorted_animation_time = ctx.input(|i| i.time);
+    let mut flat_list = state.list.clone().into_iter().flatten().collect::<Vec<Element<T>>>();
+    let finished = state.algorithm.as_ref().is_none_or(|algorithm| algorithm.is_finished());
+    if !state.sorted && state.goal.is_reached(&flat_list, &state.sorted_list, finished) {
+        if state.mode == Mode::Shuffling {
+            state.record_shuffle(&flat_list);
+        }
+
+        if state.mode == Mode::Shuffling && state.repeat_shuffles && state.running {
+            state.seed = state.seed.wrapping_add(1);
+            state.shuffle();
+        } else {
+            state.sorted = true;
+            state.running = false;
+            state.sorted_animation_time = ctx.input(|i| i.time);
+        }
+
+        // Stability only shows when there are equal elements to keep in order
+        let has_equals = state.sorted_list.windows(2).any(|pair| pair[0] == pair[1]);
+        if state.mode == Mode::Sorting && state.goal == Goal::Sorted && has_equals {
+            let rebuilt = state.algorithm.as_ref().is_some_and(|algorithm| !algorithm.moves_elements());
+            state.stable = Some(if rebuilt {
+                Stability::NotApplicable
+            } else if Element::is_in_original_order(&flat_list) {
+                Stability::Stable
+            } else {
+                Stability::Unstable
+            });
+        }
     } else if flat_list.len() != state.sorted_list.len() {
         flat_list.sort_unstable();
         state.sorted_list = flat_list;
     }
 
-    if let Some(algorithm) = &mut state.algorithm {
-        if state.running && time::SystemTime::now().duration_since(state.time_of_last_step).unwrap() > state.delay {
-            state.time_of_last_step = time::SystemTime::now();
-            algorithm.step();
-        }
+    if state.running && time::SystemTime::now().duration_since(state.time_of_last_step).unwrap() > state.delay {
+        state.step_algorithm();
+    }
+}
+
+// Racing logic. Every racer is checked against the same sorted list, since
+// they all started from the same one.
+fn race_update<T: Item>(state: &mut ProgramState<T>, ctx: &egui::Context) {
+    if state.list.iter().flatten().count() != state.sorted_list.len() {
+        let mut sorted_list = state.list.concat();
+        sorted_list.sort_unstable();
+        state.sorted_list = sorted_list;
+    }
+    state.race.check(&state.sorted_list);
+
+    if state.race.racers.len() < race::MIN_RACERS {
+        state.running = false;
+    } else if !state.sorted && state.race.is_over() {
+        state.sorted = true;
+        state.running = false;
+        state.sorted_animation_time = ctx.input(|i| i.time);
+    }
+
+    if state.running && time::SystemTime::now().duration_since(state.time_of_last_step).unwrap() > state.delay {
+        state.step_algorithm();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The values and tags of the list, which are what a run depends on.
+    fn contents<T: Item>(state: &ProgramState<T>) -> Vec<(T, usize)> {
+        state.list.iter().flatten().map(|element| (element.value.clone(), element.tag)).collect()
+    }
+
+    #[test]
+    fn switching_algorithms_keeps_the_seeded_list() {
+        let algorithm = |name: &str| {
+            sorting_algorithms::get_available_algorithms::<usize>()
+                .into_iter()
+                .find(|algorithm| algorithm.get_name() == name)
+                .unwrap()
+        };
+        let mut switched = ProgramState::<usize> { seed: 7, ..ProgramState::default() };
+        switched.select_algorithm(algorithm("Merge Sort"));
+        let first = contents(&switched);
+        switched.select_algorithm(algorithm("Tree Sort"));
+
+        let mut direct = ProgramState::<usize> { seed: 7, ..ProgramState::default() };
+        direct.select_algorithm(algorithm("Tree Sort"));
+
+        assert_eq!(contents(&switched), first);
+        assert_eq!(contents(&direct), first);
+        assert_ne!(first, (1..=first.len()).map(|value| (value, value - 1)).collect::<Vec<_>>());
     }
 }
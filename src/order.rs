@@ -0,0 +1,93 @@
+use std::cmp;
+use crate::items::Item;
+
+/// What the items are compared by.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Key {
+    /// The items themselves.
+    #[default]
+    Value,
+    /// The remainder of the item divided by the number.
+    Modulo(u64),
+    /// How many bits are set in the item.
+    BitCount,
+    /// The last decimal digit of the item.
+    LastDigit,
+}
+
+impl Key {
+    pub const ALL: [Self; 4] = [
+        Self::Value,
+        Self::Modulo(3),
+        Self::BitCount,
+        Self::LastDigit,
+    ];
+
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Value => "By Value",
+            Self::Modulo(_) => "By Value Mod k",
+            Self::BitCount => "By Bit Count",
+            Self::LastDigit => "By Last Digit",
+        }
+    }
+
+    /// The key of the item, for the keys that aren't the item itself. Only
+    /// items that are integers have them.
+    fn of<T: Item>(self, value: &T) -> Option<u64> {
+        let integer = value.as_integer()?;
+
+        match self {
+            Self::Value => None,
+            Self::Modulo(k) => Some(u64::try_from(integer.rem_euclid(i64::try_from(k.max(1)).ok()?)).ok()?),
+            Self::BitCount => Some(integer.unsigned_abs().count_ones().into()),
+            Self::LastDigit => Some(integer.unsigned_abs() % 10),
+        }
+    }
+
+    /// Whether two keys are the same kind of key, whatever their numbers.
+    pub const fn is_same_kind(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Value, Self::Value)
+                | (Self::Modulo(_), Self::Modulo(_))
+                | (Self::BitCount, Self::BitCount)
+                | (Self::LastDigit, Self::LastDigit)
+        )
+    }
+}
+
+/// How a list should be sorted, which every element of the list carries
+/// along with it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Order {
+    pub key: Key,
+    pub descending: bool,
+}
+
+impl Order {
+    pub fn compare<T: Item>(self, first: &T, second: &T) -> cmp::Ordering {
+        let ordering = match (self.key.of(first), self.key.of(second)) {
+            (Some(first), Some(second)) => first.cmp(&second),
+            _ => first.cmp(second),
+        };
+
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    /// The height of the item going by the order, which goes up with it like
+    /// `Item::height` does with the items' own order.
+    pub fn height<T: Item>(self, value: &T) -> f32 {
+        let height = self.key.of(value).map_or_else(|| value.height(), |key| key as f32);
+
+        if self.descending {
+            -height
+        } else {
+            height
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::path::Path;
+use rand::prelude::*;
+
+/// How many pixels the longer side of an image is shrunk down to, so that
+/// every pixel can still be seen.
+const MAX_SIDE: u32 = 48;
+
+/// How an image is scrambled before it's sorted back together. Each row is
+/// scrambled on its own, so that the rows can be sorted side by side.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Scramble {
+    /// The columns are shuffled, the same way in every row.
+    Columns,
+    /// The pixels of every row are shuffled.
+    Pixels,
+}
+
+impl Scramble {
+    pub const ALL: [Self; 2] = [Self::Columns, Self::Pixels];
+
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Columns => "Columns",
+            Self::Pixels => "Pixels",
+        }
+    }
+}
+
+/// A small image, with its pixels in rows from the top.
+#[derive(Clone, Debug)]
+pub struct PixelImage {
+    width: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl PixelImage {
+    /// The program's icon, so that there's an image before one is loaded.
+    pub fn builtin() -> Self {
+        let icon = include_bytes!("../icon.png");
+
+        Self::from_image(&image::load_from_memory(icon).unwrap())
+    }
+
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let image = image::open(path).map_err(|error| format!("Couldn't load {}: {error}", path.display()))?;
+
+        Ok(Self::from_image(&image))
+    }
+
+    fn from_image(image: &image::DynamicImage) -> Self {
+        let image = image.thumbnail(MAX_SIDE, MAX_SIDE).to_rgb8();
+
+        Self {
+            width: image.width() as usize,
+            pixels: image.pixels().map(|pixel| pixel.0).collect(),
+        }
+    }
+
+    /// The rows of the image scrambled, with each pixel numbered by where it
+    /// was in the image.
+    pub fn scramble(&self, scramble: Scramble, rng: &mut impl Rng) -> Vec<Vec<(usize, [u8; 3])>> {
+        let mut columns = (0..self.width).collect::<Vec<_>>();
+        columns.shuffle(rng);
+
+        self.pixels
+            .chunks(self.width.max(1))
+            .enumerate()
+            .map(|(row, pixels)| {
+                if scramble == Scramble::Pixels {
+                    columns.shuffle(rng);
+                }
+
+                columns.iter().map(|column| (row * self.width + column, pixels[*column])).collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{
+    items::Item,
+    sorting_algorithms::{Element, SortingAlgorithm},
+};
+
+/// The fewest algorithms that make a race, and the most that fit on screen.
+pub const MIN_RACERS: usize = 2;
+pub const MAX_RACERS: usize = 9;
+
+/// What the shared clock of a race hands out to each racer every tick.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Clock {
+    /// One step each. Steps do very different amounts of work from one
+    /// algorithm to the next, e.g. a step of merge sort merges two whole
+    /// lists while a step of quickselect makes one comparison.
+    Steps,
+    /// The same budget of operations each, spent going by the cost model.
+    Operations(CostModel),
+}
+
+impl Clock {
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Steps => "Steps",
+            Self::Operations(_) => "Operations",
+        }
+    }
+}
+
+/// What comparisons and writes cost, and how much a racer gets to spend on
+/// them each tick.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CostModel {
+    pub per_tick: f32,
+    pub comparison: f32,
+    pub write: f32,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            per_tick: 10.0,
+            comparison: 1.0,
+            write: 1.0,
+        }
+    }
+}
+
+impl CostModel {
+    /// What a step making the comparisons and writes costs. Every step costs
+    /// at least the cheaper of the two, so that steps which only move things
+    /// around off the list still use up the budget.
+    pub fn cost(self, comparisons: usize, writes: usize) -> f32 {
+        (comparisons as f32)
+            .mul_add(self.comparison, writes as f32 * self.write)
+            .max(self.comparison.min(self.write))
+    }
+}
+
+/// A sorting algorithm in a race, and how far it has got.
+pub struct Racer<T> {
+    pub algorithm: Box<dyn SortingAlgorithm<T>>,
+    pub steps: usize,
+    pub comparisons: usize,
+    pub writes: usize,
+    /// The operations the racer's steps have cost, when the clock hands
+    /// them out. This stops going up once the racer finishes, which tells
+    /// apart the racers that finish on the same tick.
+    pub spent: f32,
+    /// What's left of the racer's budget of operations, which goes below
+    /// zero when the last step cost more than there was left.
+    budget: f32,
+    /// The tick the racer finished on, once it has.
+    pub finish: Option<usize>,
+}
+
+impl<T: Item> Racer<T> {
+    fn new(algorithm: Box<dyn SortingAlgorithm<T>>) -> Self {
+        Self {
+            algorithm,
+            steps: 0,
+            comparisons: 0,
+            writes: 0,
+            spent: 0.0,
+            budget: 0.0,
+            finish: None,
+        }
+    }
+
+    /// Take a step, counting the comparisons and writes the algorithm says it
+    /// made.
+    fn step(&mut self) -> (usize, usize) {
+        self.algorithm.step();
+        let accesses = self.algorithm.get_accesses();
+        let (comparisons, writes) = (accesses.comparisons, accesses.write_count());
+
+        self.steps += 1;
+        self.comparisons += comparisons;
+        self.writes += writes;
+
+        (comparisons, writes)
+    }
+
+    fn has_reached_goal(&self, sorted: &[Element<T>]) -> bool {
+        let list = self.algorithm.get_list().0.concat();
+
+        self.algorithm.get_goal().is_reached(&list, sorted, self.algorithm.is_finished())
+    }
+}
+
+/// Sorting algorithms racing each other on copies of the same list. They
+/// share a clock, which hands out the same amount to each racer every tick.
+pub struct Race<T> {
+    pub racers: Vec<Racer<T>>,
+    pub clock: Clock,
+    pub ticks: usize,
+    /// The indexes of the racers that have finished, in the order they did.
+    pub finish_order: Vec<usize>,
+}
+
+impl<T: Item> Default for Race<T> {
+    fn default() -> Self {
+        Self {
+            racers: vec![],
+            clock: Clock::Steps,
+            ticks: 0,
+            finish_order: vec![],
+        }
+    }
+}
+
+impl<T: Item> Race<T> {
+    /// Add the algorithm to the race, or take it out if it's already in it.
+    /// Nothing is added once the race is full.
+    pub fn toggle(&mut self, algorithm: Box<dyn SortingAlgorithm<T>>) {
+        if let Some(index) = self.racers.iter().position(|racer| racer.algorithm.get_name() == algorithm.get_name()) {
+            self.racers.remove(index);
+        } else if self.racers.len() < MAX_RACERS {
+            self.racers.push(Racer::new(algorithm));
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.racers.iter().any(|racer| racer.algorithm.get_name() == name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.racers.iter().map(|racer| racer.algorithm.get_name().to_string()).collect()
+    }
+
+    /// Start every racer over on the list, all with the same seed.
+    pub fn restart(&mut self, list: &[Vec<Element<T>>], seed: u64) {
+        for racer in &mut self.racers {
+            racer.algorithm.set_seed(seed);
+            racer.algorithm.set_list(list.to_vec());
+            racer.steps = 0;
+            racer.comparisons = 0;
+            racer.writes = 0;
+            racer.spent = 0.0;
+            racer.budget = 0.0;
+            racer.finish = None;
+        }
+        self.ticks = 0;
+        self.finish_order = vec![];
+    }
+
+    pub fn set_target(&mut self, target: usize) {
+        for racer in &mut self.racers {
+            racer.algorithm.set_target(target);
+        }
+    }
+
+    /// Move every racer that hasn't finished yet along by what the clock
+    /// hands out, given the sorted list. A racer that runs over its budget of
+    /// operations has less to spend on the next tick.
+    pub fn tick(&mut self, sorted: &[Element<T>]) {
+        let clock = self.clock;
+        let tick = self.ticks + 1;
+        for racer in self.racers.iter_mut().filter(|racer| racer.finish.is_none()) {
+            match clock {
+                Clock::Steps => {
+                    racer.step();
+                },
+                // The goal is checked after every step, so that a racer stops
+                // spending as soon as it gets there
+                Clock::Operations(cost_model) => {
+                    racer.budget += cost_model.per_tick;
+                    while racer.budget > 0.0 && !racer.algorithm.is_finished() {
+                        let (comparisons, writes) = racer.step();
+                        let cost = cost_model.cost(comparisons, writes);
+                        racer.spent += cost;
+                        racer.budget -= cost;
+
+                        if racer.has_reached_goal(sorted) {
+                            racer.finish = Some(tick);
+                            break;
+                        }
+                    }
+                },
+            }
+        }
+        self.ticks = tick;
+
+        self.check(sorted);
+    }
+
+    /// Note down the racers that have reached their goals since the last
+    /// check, given the sorted list. Racers that got there on the same tick
+    /// are put in the order of what they spent getting there.
+    pub fn check(&mut self, sorted: &[Element<T>]) {
+        for racer in self.racers.iter_mut().filter(|racer| racer.finish.is_none()) {
+            if racer.has_reached_goal(sorted) {
+                racer.finish = Some(self.ticks);
+            }
+        }
+
+        let mut finished = (0..self.racers.len())
+            .filter(|index| self.racers[*index].finish.is_some() && !self.finish_order.contains(index))
+            .collect::<Vec<_>>();
+        finished.sort_by(|first, second| self.racers[*first].spent.total_cmp(&self.racers[*second].spent));
+        self.finish_order.extend(finished);
+    }
+
+    /// The place the racer finished in. Racers that finished on the same tick
+    /// having spent the same share a place, which is every racer that
+    /// finished on that tick when the clock hands out steps.
+    pub fn place(&self, index: usize) -> Option<usize> {
+        let racer = &self.racers[index];
+        let finish = racer.finish?;
+        let ahead = |other: &Racer<T>| {
+            other.finish.is_some_and(|other_finish| other_finish < finish || (other_finish == finish && other.spent < racer.spent))
+        };
+
+        Some(1 + self.racers.iter().filter(|other| ahead(other)).count())
+    }
+
+    pub fn is_over(&self) -> bool {
+        !self.racers.is_empty() && self.racers.iter().all(|racer| racer.finish.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting_algorithms::get_available_algorithms;
+
+    fn race(names: &[&str], clock: Clock) -> Race<usize> {
+        let mut race = Race { clock, ..Race::default() };
+        for algorithm in get_available_algorithms() {
+            if names.contains(&algorithm.get_name()) {
+                race.toggle(algorithm);
+            }
+        }
+
+        race
+    }
+
+    #[test]
+    fn racers_finishing_on_the_same_tick_go_in_the_order_of_their_costs() {
+        // A reversed list all goes on one pile of patience sort, so it
+        // compares far less than merge sort does
+        let clock = Clock::Operations(CostModel { per_tick: 100_000.0, ..CostModel::default() });
+        let mut race = race(&["Merge Sort", "Patience Sort"], clock);
+        let list = vec![Element::tagged((1..=32).rev())];
+        let sorted = Element::tagged(1..=32);
+        race.restart(&list, 0);
+        race.tick(&sorted);
+
+        let [merge, patience] = [0, 1].map(|index| &race.racers[index]);
+        assert_eq!(merge.algorithm.get_name(), "Merge Sort");
+        assert_eq!((merge.finish, patience.finish), (Some(1), Some(1)));
+        assert!(patience.spent < merge.spent);
+        assert_eq!(race.finish_order, [1, 0]);
+        assert_eq!((race.place(1), race.place(0)), (Some(1), Some(2)));
+    }
+}
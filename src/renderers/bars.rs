@@ -0,0 +1,245 @@
+use super::{Renderer, Scene, BAR_COLORS};
+
+use std::collections::HashMap;
+use eframe::egui::{self, epaint};
+use crate::{items::Item, sorting_algorithms::{height_floor, Element, Highlight}};
+
+/// One bar per element, as tall as its value, standing on strips that say
+/// which list the element is in.
+pub struct Bars {
+    base_height: f32,
+    base_spacing: f32,
+}
+
+impl Default for Bars {
+    fn default() -> Self {
+        Self {
+            base_height: 10.0,
+            base_spacing: 10.0,
+        }
+    }
+}
+
+impl<T: Item> Renderer<T> for Bars {
+    fn get_name(&self) -> &'static str {
+        "Bars"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let (list, base_height, base_spacing) = (scene.list, self.base_height, self.base_spacing);
+        let mut bars = vec![];
+        let max_height = rect.height() - base_height - 25.0;
+
+        let filled_in = list.iter()
+            .map(|l| vec![true; l.len()])
+            .collect::<Vec<Vec<bool>>>()
+            .join(&[false][..]);
+        let bar_width = rect.width() / filled_in.len() as f32;
+        let mut color_index = 0;
+        let mut flat_index = 0;
+        for slot in filled_in.iter().enumerate() {
+            if *slot.1 {
+                let color = if scene.goal.is_target(flat_index) {
+                    epaint::Color32::LIGHT_BLUE
+                } else if list.len() == 1 {
+                    epaint::Color32::DARK_GRAY
+                } else {
+                    BAR_COLORS[color_index % BAR_COLORS.len()]
+                };
+                flat_index += 1;
+
+                let base = epaint::Shape::rect_filled(epaint::Rect::from_two_pos(
+                    epaint::pos2((slot.0 as f32).mul_add(bar_width, rect.left()), rect.bottom()),
+                    epaint::pos2(((slot.0 + 1) as f32).mul_add(bar_width, rect.left()), rect.bottom() - base_height),
+                ), 0.0, color);
+
+                bars.push(base);
+            } else {
+                color_index += 1;
+            }
+        }
+
+        if list.is_empty() {
+            return bars;
+        }
+
+        let max_value = list.iter().flatten().map(|element| element.value.height()).fold(f32::NEG_INFINITY, f32::max);
+        let min_value = height_floor(list.iter().flatten().map(|element| element.value.height()));
+        let list_directory = list
+            .iter()
+            .map(|v| 0..v.len())
+            .scan(0, |len, list| {
+                let new_list = list
+                    .clone()
+                    .map(|i| i + *len)
+                    .collect::<Vec<_>>();
+                *len += list.len() + 1; // Add one to account for the 0 values
+                                        // in between
+                Some(new_list)
+            })
+            .collect::<Vec<_>>();
+        let highlight_indices = scene.highlights
+            .iter()
+            // If there is an error here you probably set your highlights up
+            // wrong
+            .map(|((first_index, second_index), highlight)| (list_directory[*first_index][*second_index], *highlight))
+            .collect::<Vec<_>>();
+        let places = make_places(scene, &list_directory, filled_in.len());
+        let group_indices = scene.groups
+            .iter()
+            .map(|((first_index, second_index), group)| (list_directory[*first_index][*second_index], *group))
+            .collect::<Vec<_>>();
+
+        // Equal elements are shaded darker the later they started out
+        let heights = list
+            .iter()
+            .map(|sublist| sublist.iter().map(|element| element.value.height()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let shades = make_shades(list).join(&[0.0][..]);
+        for (number, shade) in heights.join(&[min_value][..]).into_iter().enumerate().zip(shades) {
+            let bar_height = ((number.1 - min_value) / (max_value - min_value)) * max_height;
+            let color = if scene.flashing_sorted {
+                epaint::Color32::LIGHT_GREEN
+            } else if let Some(highlight) = highlight_indices.iter().filter(|(index, _)| *index == number.0).map(|(_, highlight)| *highlight).max() {
+                highlight.color()
+            } else if let Some((_, group)) = group_indices.iter().find(|(index, _)| *index == number.0) {
+                BAR_COLORS[group % BAR_COLORS.len()]
+            } else {
+                epaint::Color32::WHITE.lerp_to_gamma(epaint::Color32::from_rgb(90, 110, 160), shade)
+            };
+
+            let bar = epaint::Shape::rect_filled(
+                egui::Rect::from_two_pos(
+                    epaint::pos2(bar_width.mul_add(places[number.0], rect.left()), rect.bottom() - base_height - base_spacing),
+                    epaint::pos2(bar_width.mul_add(places[number.0] + 1.0, rect.left()), rect.bottom() - base_height - base_spacing - bar_height),
+                ),
+                epaint::Rounding::ZERO,
+                color,
+            );
+            bars.push(bar);
+        }
+
+        if T::SHOW_LABELS {
+            let bottom = rect.bottom() - base_height - base_spacing;
+            let compared = highlight_indices
+                .iter()
+                .filter(|(_, highlight)| *highlight == Highlight::Compare)
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>();
+            bars.extend(make_labels(scene, &list_directory, &places, &compared, (rect.left(), bottom), bar_width, ctx));
+        }
+
+        bars
+    }
+}
+
+/// How far each element is through the elements equal to it, going by
+/// where they started out, from 0 for the first to 1 for the last.
+/// Elements with nothing equal to them are at 0.
+fn make_shades<T: Item>(list: &[Vec<Element<T>>]) -> Vec<Vec<f32>> {
+    let element = |(list_index, index): (usize, usize)| &list[list_index][index];
+
+    // Sorting by value and then by tag puts equal elements next to each
+    // other, in the order they started out in
+    let mut positions = list
+        .iter()
+        .enumerate()
+        .flat_map(|(list_index, sublist)| (0..sublist.len()).map(move |index| (list_index, index)))
+        .collect::<Vec<_>>();
+    positions.sort_by(|a, b| element(*a).cmp(element(*b)).then(element(*a).tag.cmp(&element(*b).tag)));
+
+    let mut shades = list.iter().map(|sublist| vec![0.0; sublist.len()]).collect::<Vec<_>>();
+    for equals in positions.chunk_by(|a, b| element(*a) == element(*b)) {
+        for (rank, (list_index, index)) in equals.iter().enumerate() {
+            shades[*list_index][*index] = rank as f32 / equals.len().saturating_sub(1).max(1) as f32;
+        }
+    }
+
+    shades
+}
+
+/// Where each slot's bar is drawn, counted in bars from the left. Bars that
+/// just moved slide over from the slot they were in before the last step,
+/// going by their tags.
+fn make_places<T: Item>(scene: &Scene<'_, T>, list_directory: &[Vec<usize>], slots: usize) -> Vec<f32> {
+    let mut places = (0..slots).map(|slot| slot as f32).collect::<Vec<_>>();
+    let Some((previous, progress)) = scene.motion else {
+        return places;
+    };
+
+    // The previous list gets its slots the same way, with a gap between
+    // each of its lists
+    let mut previous_slots = HashMap::new();
+    let mut slot = 0;
+    for list in previous {
+        for element in list {
+            previous_slots.insert(element.tag, slot);
+            slot += 1;
+        }
+        slot += 1;
+    }
+
+    // Easing in and out keeps the bars from jerking into motion
+    let eased = progress * progress * 2.0f32.mul_add(-progress, 3.0);
+    for (element, slot) in scene.list.iter().flatten().zip(list_directory.iter().flatten()) {
+        if let Some(previous_slot) = previous_slots.get(&element.tag) {
+            places[*slot] = (*slot as f32 - *previous_slot as f32).mul_add(eased, *previous_slot as f32);
+        }
+    }
+
+    places
+}
+
+/// Write each element up its bar, marking the character being compared
+/// in the highlighted ones.
+fn make_labels<T: Item>(
+    scene: &Scene<'_, T>,
+    list_directory: &[Vec<usize>],
+    places: &[f32],
+    highlight_indices: &[usize],
+    (left, bottom): (f32, f32),
+    bar_width: f32,
+    ctx: &egui::Context,
+) -> Vec<epaint::Shape> {
+    // Only label the bars if the labels will fit
+    let font_size = (bar_width * 0.7).min(16.0);
+    if font_size < 6.0 {
+        return vec![];
+    }
+    let format = |background| egui::TextFormat {
+        font_id: egui::FontId::monospace(font_size),
+        color: epaint::Color32::BLACK,
+        background,
+        ..Default::default()
+    };
+
+    let mut labels = vec![];
+    for (element, slot) in scene.list.iter().flatten().zip(list_directory.iter().flatten()) {
+        let text = element.value.to_string();
+
+        let mut job = egui::text::LayoutJob::default();
+        let marked = scene.character
+            .filter(|_| highlight_indices.contains(slot))
+            .and_then(|position| text.get(position..)?.chars().next().map(|character| position..position + character.len_utf8()));
+        if let Some(marked) = marked {
+            job.append(&text[..marked.start], 0.0, format(epaint::Color32::TRANSPARENT));
+            job.append(&text[marked.clone()], 0.0, format(epaint::Color32::YELLOW));
+            job.append(&text[marked.end..], 0.0, format(epaint::Color32::TRANSPARENT));
+        } else {
+            job.append(&text, 0.0, format(epaint::Color32::TRANSPARENT));
+        }
+
+        // Turned to read from the bottom of the bar up, which puts the
+        // height of the text to the right of where it starts
+        let galley = ctx.fonts(|fonts| fonts.layout_job(job));
+        let position = epaint::pos2(
+            bar_width.mul_add(places[*slot] + 0.5, left) - galley.size().y / 2.0,
+            bottom - 2.0,
+        );
+        labels.push(epaint::TextShape::new(position, galley, epaint::Color32::BLACK)
+            .with_angle(-std::f32::consts::FRAC_PI_2)
+            .into());
+    }
+
+    labels
+}
@@ -0,0 +1,58 @@
+use super::{Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::items::Item;
+
+/// A grid of beads threaded on vertical rods, for bead sort. Each row of the
+/// grid says which of the rods have a bead at that row.
+pub struct Beads<'a> {
+    pub grid: &'a [Vec<bool>],
+}
+
+impl<T: Item> Renderer<T> for Beads<'_> {
+    fn get_name(&self) -> &'static str {
+        "Beads"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let grid = self.grid;
+        let mut shapes = vec![];
+        let rods = grid.first().map_or(0, Vec::len);
+        if rods == 0 {
+            return shapes;
+        }
+
+        let cell = egui::vec2(rect.width() / rods as f32, rect.height() / grid.len() as f32);
+        let color = if scene.flashing_sorted {
+            epaint::Color32::LIGHT_GREEN
+        } else {
+            epaint::Color32::WHITE
+        };
+
+        // Only draw the rods while there's room between them
+        if cell.x >= 4.0 {
+            for rod in 0..rods {
+                let x = (rod as f32 + 0.5).mul_add(cell.x, rect.left());
+                shapes.push(epaint::Shape::line_segment(
+                    [epaint::pos2(x, rect.top()), epaint::pos2(x, rect.bottom())],
+                    epaint::Stroke::new(1.0, epaint::Color32::DARK_GRAY),
+                ));
+            }
+        }
+
+        for (row_index, row) in grid.iter().enumerate() {
+            for (rod, _) in row.iter().enumerate().filter(|(_, bead)| **bead) {
+                let center = rect.left_top() + cell * egui::vec2(rod as f32 + 0.5, row_index as f32 + 0.5);
+                let size = cell * 0.8;
+
+                shapes.push(epaint::Shape::rect_filled(
+                    egui::Rect::from_center_size(center, size),
+                    epaint::Rounding::same(size.min_elem() / 2.0),
+                    color,
+                ));
+            }
+        }
+
+        shapes
+    }
+}
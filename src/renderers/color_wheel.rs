@@ -0,0 +1,32 @@
+use super::{point_around, Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::items::Item;
+
+/// A disc with one slice per element going around it, colored by the
+/// element's value. Sorted lists go smoothly around the color wheel.
+pub struct ColorWheel;
+
+impl<T: Item> Renderer<T> for ColorWheel {
+    fn get_name(&self) -> &'static str {
+        "Color Wheel"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let hues = scene.hues();
+        let center = rect.center();
+        let radius = rect.width().min(rect.height()) * 0.45;
+
+        // One triangle per element, from the center out to the edge
+        let mut mesh = epaint::Mesh::default();
+        for (slice, color) in hues.iter().enumerate() {
+            let first = mesh.vertices.len() as u32;
+            mesh.colored_vertex(center, *color);
+            mesh.colored_vertex(point_around(center, slice, hues.len(), radius), *color);
+            mesh.colored_vertex(point_around(center, slice + 1, hues.len(), radius), *color);
+            mesh.add_triangle(first, first + 1, first + 2);
+        }
+
+        vec![epaint::Shape::mesh(mesh)]
+    }
+}
@@ -0,0 +1,34 @@
+use super::{point_around, Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::items::Item;
+
+/// One dot per element going around a circle, further out the closer the
+/// element is to where it belongs. Sorted lists are a ring.
+pub struct DisparityDots;
+
+impl<T: Item> Renderer<T> for DisparityDots {
+    fn get_name(&self) -> &'static str {
+        "Disparity Dots"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let hues = scene.hues();
+        let count = hues.len();
+        let center = rect.center();
+        let radius = rect.width().min(rect.height()) * 0.45;
+        let dot_radius = (std::f32::consts::PI * radius / count.max(1) as f32).clamp(1.5, 6.0);
+
+        let mut shapes = vec![epaint::Shape::circle_stroke(center, radius, epaint::Stroke::new(1.0, epaint::Color32::DARK_GRAY))];
+        for ((index, sorted_position), color) in scene.sorted_positions().into_iter().enumerate().zip(hues) {
+            // The distance is taken around the circle, so the elements half
+            // the list away from where they belong are in the middle
+            let offset = index.abs_diff(sorted_position);
+            let disparity = offset.min(count - offset) as f32 / count as f32;
+            let distance = radius * 2.0f32.mul_add(-disparity, 1.0);
+            shapes.push(epaint::Shape::circle_filled(point_around(center, index, count, distance), dot_radius, color));
+        }
+
+        shapes
+    }
+}
@@ -0,0 +1,56 @@
+use super::{Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::{items::Item, sorting_algorithms::Element};
+
+/// One cell per element in a grid, each list being a row of it, going from
+/// cold for the lowest values to hot for the highest.
+pub struct Grid<'a> {
+    /// The row and column of each element's cell, since cells can be empty.
+    pub cells: &'a [(usize, usize)],
+}
+
+impl<T: Item> Renderer<T> for Grid<'_> {
+    fn get_name(&self) -> &'static str {
+        "Grid"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let cells = self.cells;
+        let mut shapes = vec![];
+        let rows = cells.iter().map(|(row, _)| row + 1).max().unwrap_or(0);
+        let columns = cells.iter().map(|(_, column)| column + 1).max().unwrap_or(0);
+        if rows == 0 {
+            return shapes;
+        }
+
+        // The cells are square, and go from cold for the lowest values to hot
+        // for the highest
+        let size = (rect.width() / columns as f32).min(rect.height() / rows as f32);
+        let corner = rect.center() - size * egui::vec2(columns as f32, rows as f32) / 2.0;
+        let heights = scene.list.iter().flatten().map(Element::height).collect::<Vec<_>>();
+        let min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        for ((position, (row, column)), height) in scene.positions().zip(cells).zip(heights) {
+            let fraction = if min < max { (height - min) / (max - min) } else { 0.0 };
+            let color = if scene.flashing_sorted {
+                epaint::Color32::LIGHT_GREEN
+            } else {
+                epaint::Hsva::new((1.0 - fraction) * 0.66, 0.85, 0.95, 1.0).into()
+            };
+            let cell = egui::Rect::from_min_size(corner + size * egui::vec2(*column as f32, *row as f32), egui::vec2(size, size));
+            shapes.push(epaint::Shape::rect_filled(cell.shrink(size * 0.05), epaint::Rounding::same(size * 0.1), color));
+
+            if let Some(highlight) = scene.highlight(position) {
+                shapes.push(epaint::Shape::rect_stroke(
+                    cell.shrink(size * 0.05),
+                    epaint::Rounding::same(size * 0.1),
+                    epaint::Stroke::new(2.0, highlight.color()),
+                ));
+            }
+        }
+
+        shapes
+    }
+}
@@ -0,0 +1,50 @@
+use super::{Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::items::Item;
+
+/// One square cell per element with each list as a row of them, colored by
+/// the element's own color if it has one. Sorting the pixels of a scrambled
+/// image puts the picture back together.
+pub struct Image;
+
+impl<T: Item> Renderer<T> for Image {
+    fn get_name(&self) -> &'static str {
+        "Image"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let columns = scene.list.iter().map(Vec::len).max().unwrap_or(0);
+        let rows = scene.list.len();
+        if columns == 0 {
+            return vec![];
+        }
+
+        let cell = (rect.width() / columns as f32).min(rect.height() / rows as f32);
+        let corner = rect.center() - cell * egui::vec2(columns as f32, rows as f32) / 2.0;
+
+        let mut shapes = vec![];
+        let mut outlines = vec![];
+        for (((row, column), element), hue) in scene.positions().zip(scene.list.iter().flatten()).zip(scene.hues()) {
+            let color = match element.value.color() {
+                Some([r, g, b]) if !scene.flashing_sorted => epaint::Color32::from_rgb(r, g, b),
+                _ => hue,
+            };
+            let cell_rect = egui::Rect::from_min_size(corner + cell * egui::vec2(column as f32, row as f32), egui::vec2(cell, cell));
+            shapes.push(epaint::Shape::rect_filled(cell_rect, epaint::Rounding::ZERO, color));
+
+            // Outlines go on top, so that the cells next to them don't hide
+            // them
+            if let Some(highlight) = scene.highlight((row, column)) {
+                outlines.push(epaint::Shape::rect_stroke(
+                    cell_rect,
+                    epaint::Rounding::ZERO,
+                    epaint::Stroke::new(2.0, highlight.color()),
+                ));
+            }
+        }
+        shapes.extend(outlines);
+
+        shapes
+    }
+}
@@ -0,0 +1,86 @@
+use super::{Renderer, Scene, BAR_COLORS};
+
+use eframe::egui::{self, epaint};
+use crate::{items::Item, sorting_algorithms::Highlight};
+
+/// One node per element, each list being a chain of nodes linked by arrows.
+pub struct Links<'a> {
+    /// Where each node is in memory, so that the nodes stay put while they're
+    /// relinked.
+    pub slots: &'a [usize],
+}
+
+impl<T: Item> Renderer<T> for Links<'_> {
+    fn get_name(&self) -> &'static str {
+        "Links"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let slots = self.slots;
+        let mut shapes = vec![];
+
+        // The nodes sit in a row by where they are in memory, with the
+        // pointers going forward arcing over them and the ones going back
+        // arcing under them
+        let spacing = rect.width() / slots.len().max(1) as f32;
+        let radius = (spacing * 0.35).min(16.0);
+        let center = |slot: usize| epaint::pos2((slot as f32 + 0.5).mul_add(spacing, rect.left()), rect.center().y);
+        let positions = scene.positions().zip(slots.iter().copied()).collect::<Vec<_>>();
+
+        for pair in positions.windows(2) {
+            let (((list_index, index), from), ((next_list_index, _), to)) = (pair[0], pair[1]);
+            if list_index != next_list_index {
+                continue;
+            }
+
+            let color = scene.highlight((list_index, index)).map_or(epaint::Color32::GRAY, Highlight::color);
+            let side = if to > from { -1.0 } else { 1.0 };
+            let start = center(from) + egui::vec2(0.0, side * radius);
+            let end = center(to) + egui::vec2(0.0, side * radius);
+            let bulge = ((end.x - start.x).abs() * 0.3).min(rect.height().mul_add(0.4, -radius));
+            let control = epaint::pos2(f32::midpoint(start.x, end.x), (2.0 * bulge).mul_add(side, start.y));
+            shapes.push(epaint::QuadraticBezierShape::from_points_stroke(
+                [start, control, end],
+                false,
+                epaint::Color32::TRANSPARENT,
+                epaint::Stroke::new(1.5, color),
+            ).into());
+
+            // The arrowhead points the way the curve comes in
+            let direction = (end - control).normalized() * (radius * 0.6).max(4.0);
+            for angle in [0.5, -0.5] {
+                shapes.push(epaint::Shape::line_segment(
+                    [end - egui::emath::Rot2::from_angle(angle) * direction, end],
+                    epaint::Stroke::new(1.5, color),
+                ));
+            }
+        }
+
+        for ((list_index, index), slot) in positions {
+            let color = if scene.flashing_sorted {
+                epaint::Color32::LIGHT_GREEN
+            } else if let Some(highlight) = scene.highlight((list_index, index)) {
+                highlight.color()
+            } else if scene.list.len() == 1 {
+                epaint::Color32::WHITE
+            } else {
+                BAR_COLORS[list_index % BAR_COLORS.len()]
+            };
+            shapes.push(epaint::Shape::circle_filled(center(slot), radius, color));
+
+            // Only label the nodes if the labels will fit
+            if radius >= 8.0 {
+                shapes.push(ctx.fonts(|fonts| epaint::Shape::text(
+                    fonts,
+                    center(slot),
+                    egui::Align2::CENTER_CENTER,
+                    &scene.list[list_index][index].value,
+                    egui::FontId::monospace(radius),
+                    epaint::Color32::BLACK,
+                )));
+            }
+        }
+
+        shapes
+    }
+}
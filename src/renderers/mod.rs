@@ -0,0 +1,226 @@
+pub mod bars;
+pub mod beads;
+pub mod color_wheel;
+pub mod disparity_dots;
+pub mod grid;
+pub mod image;
+pub mod links;
+pub mod rods;
+pub mod scatter;
+pub mod spiral;
+pub mod tree;
+
+use eframe::egui::{self, epaint};
+use crate::{items::Item, sorting_algorithms::{Element, Goal, Highlight, Layout}};
+
+/// The colors that lists and groups are told apart by.
+pub const BAR_COLORS: [epaint::Color32; 12] = [
+    epaint::Color32::DARK_RED,
+    epaint::Color32::RED,
+    epaint::Color32::LIGHT_RED,
+    epaint::Color32::BROWN,
+    epaint::Color32::YELLOW,
+    epaint::Color32::GOLD,
+    epaint::Color32::DARK_GREEN,
+    epaint::Color32::GREEN,
+    epaint::Color32::LIGHT_GREEN,
+    epaint::Color32::DARK_BLUE,
+    epaint::Color32::BLUE,
+    epaint::Color32::LIGHT_BLUE,
+];
+
+pub fn get_available_renderers<T: Item>() -> Vec<Box<dyn Renderer<T>>> {
+    vec![
+        Box::new(bars::Bars::default()),
+        Box::new(scatter::Scatter),
+        Box::new(spiral::Spiral),
+        Box::new(color_wheel::ColorWheel),
+        Box::new(disparity_dots::DisparityDots),
+        Box::new(image::Image),
+    ]
+}
+
+/// Where the image renderer is in the available renderers, found by its
+/// name. Image pixels are drawn with it to begin with.
+pub fn get_image_renderer<T: Item>() -> usize {
+    let name = Renderer::<T>::get_name(&image::Image);
+
+    get_available_renderers::<T>()
+        .iter()
+        .position(|renderer| renderer.get_name() == name)
+        .expect("the image renderer is always available")
+}
+
+/// The renderer for the layout an algorithm draws its list in. Lists of bars
+/// can be drawn by any of the available renderers, so they don't have one.
+pub fn get_layout_renderer<'a, T: Item>(layout: &'a Layout) -> Option<Box<dyn Renderer<T> + 'a>> {
+    match layout {
+        Layout::Bars => None,
+        Layout::Beads(grid) => Some(Box::new(beads::Beads { grid })),
+        Layout::Rods(lifts, hand) => Some(Box::new(rods::Rods { lifts, hand: *hand })),
+        Layout::Links(slots) => Some(Box::new(links::Links { slots })),
+        Layout::Grid(cells) => Some(Box::new(grid::Grid { cells })),
+    }
+}
+
+/// Everything about the list that a renderer draws.
+pub struct Scene<'a, T> {
+    pub list: &'a [Vec<Element<T>>],
+    pub highlights: &'a [((usize, usize), Highlight)],
+    pub groups: &'a [((usize, usize), usize)],
+    pub goal: Goal,
+    /// The position of the character being compared in the highlighted
+    /// strings.
+    pub character: Option<usize>,
+    /// Whether the list has just been sorted, which flashes it.
+    pub flashing_sorted: bool,
+    /// The list before the last step, and how far the elements have got from
+    /// where they were in it to where they are now, from 0 to 1.
+    pub motion: Option<(&'a [Vec<Element<T>>], f32)>,
+}
+
+impl<T: Item> Scene<'_, T> {
+    /// What the element at the position is highlighted for, if anything.
+    pub fn highlight(&self, position: (usize, usize)) -> Option<Highlight> {
+        self.highlights
+            .iter()
+            .filter(|(highlighted, _)| *highlighted == position)
+            .map(|(_, highlight)| *highlight)
+            .max()
+    }
+
+    /// The position of every element, going through the lists in order.
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.list
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| (0..list.len()).map(move |index| (list_index, index)))
+    }
+
+    /// How far up from the lowest value to the highest each element is, from
+    /// 0 to 1.
+    fn fractions(&self) -> Vec<f32> {
+        let heights = self.list.iter().flatten().map(|element| element.value.height()).collect::<Vec<_>>();
+        let min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        heights
+            .into_iter()
+            .map(|height| if min < max { (height - min) / (max - min) } else { 0.0 })
+            .collect()
+    }
+
+    /// The color of each element going around the color wheel by its value,
+    /// with the highlighted elements in the color of their highlight.
+    fn hues(&self) -> Vec<epaint::Color32> {
+        self.fractions()
+            .into_iter()
+            .zip(self.positions())
+            .map(|(fraction, position)| if self.flashing_sorted {
+                epaint::Color32::LIGHT_GREEN
+            } else if let Some(highlight) = self.highlight(position) {
+                highlight.color()
+            } else {
+                // Stopping short of a full turn keeps the top and bottom
+                // from being the same color
+                epaint::Hsva::new(fraction * 0.85, 0.85, 0.95, 1.0).into()
+            })
+            .collect()
+    }
+
+    /// Where each element of the list would be once the list is sorted.
+    /// Equal elements keep their order, so every element has a place of its
+    /// own.
+    fn sorted_positions(&self) -> Vec<usize> {
+        let list = self.list.concat();
+        let mut indexes = (0..list.len()).collect::<Vec<_>>();
+        indexes.sort_by(|a, b| list[*a].cmp(&list[*b]));
+
+        let mut positions = vec![0; list.len()];
+        for (position, index) in indexes.into_iter().enumerate() {
+            positions[index] = position;
+        }
+
+        positions
+    }
+}
+
+/// A way of drawing a list. Renderers only draw what they're given, so the
+/// same list can be drawn by any of them.
+pub trait Renderer<T: Item> {
+    fn get_name(&self) -> &'static str;
+
+    /// Draw the list into the rect.
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, ctx: &egui::Context) -> Vec<epaint::Shape>;
+}
+
+/// The point at the angle for the index going around a circle clockwise from
+/// the top, at the distance from the center.
+fn point_around(center: epaint::Pos2, index: usize, count: usize, distance: f32) -> epaint::Pos2 {
+    let angle = std::f32::consts::TAU * index as f32 / count.max(1) as f32 - std::f32::consts::FRAC_PI_2;
+
+    center + distance * egui::vec2(angle.cos(), angle.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene<'a>(list: &'a [Vec<Element<usize>>], highlights: &'a [((usize, usize), Highlight)]) -> Scene<'a, usize> {
+        Scene {
+            list,
+            highlights,
+            groups: &[],
+            goal: Goal::Sorted,
+            character: None,
+            flashing_sorted: false,
+            motion: None,
+        }
+    }
+
+    #[test]
+    fn fractions_go_from_the_lowest_value_to_the_highest() {
+        let list = [Element::tagged([3, 1]), Element::tagged([5, 2])];
+
+        assert_eq!(scene(&list, &[]).fractions(), [0.5, 0.0, 1.0, 0.25]);
+    }
+
+    #[test]
+    fn fractions_of_equal_values_are_all_zero() {
+        let list = [Element::tagged([4, 4, 4])];
+
+        assert_eq!(scene(&list, &[]).fractions(), [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn sorted_positions_keep_equal_elements_in_order() {
+        let list = [Element::tagged([2, 1]), Element::tagged([2, 0])];
+
+        assert_eq!(scene(&list, &[]).sorted_positions(), [2, 1, 3, 0]);
+    }
+
+    #[test]
+    fn positions_go_through_the_lists_in_order() {
+        let list = [Element::tagged([1, 2]), vec![], Element::tagged([3])];
+
+        assert_eq!(scene(&list, &[]).positions().collect::<Vec<_>>(), [(0, 0), (0, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn highlight_shows_the_later_highlight() {
+        let list = [Element::tagged([1, 2, 3])];
+        let highlights = [((0, 1), Highlight::Pivot), ((0, 1), Highlight::Compare), ((0, 2), Highlight::Final)];
+        let scene = scene(&list, &highlights);
+
+        assert_eq!(scene.highlight((0, 0)), None);
+        assert_eq!(scene.highlight((0, 1)), Some(Highlight::Pivot));
+        assert_eq!(scene.highlight((0, 2)), Some(Highlight::Final));
+    }
+
+    #[test]
+    fn image_renderer_is_found_among_the_available_ones() {
+        let renderers = get_available_renderers::<crate::items::Pixel>();
+
+        assert_eq!(renderers[get_image_renderer::<crate::items::Pixel>()].get_name(), "Image");
+    }
+}
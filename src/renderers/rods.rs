@@ -0,0 +1,86 @@
+use super::{Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::{items::Item, sorting_algorithms::{height_floor, Element}};
+
+const SPAGHETTI: epaint::Color32 = epaint::Color32::from_rgb(0xf3, 0xd9, 0x8b);
+
+/// One uncooked spaghetti rod per element standing on a table, for spaghetti
+/// sort, with the hand coming down onto them if there is one.
+pub struct Rods<'a> {
+    /// How far each rod is lifted off the table.
+    pub lifts: &'a [f32],
+    /// How high the hand is.
+    pub hand: Option<f32>,
+}
+
+impl<T: Item> Renderer<T> for Rods<'_> {
+    fn get_name(&self) -> &'static str {
+        "Rods"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let (lifts, hand) = (self.lifts, self.hand);
+        let table = rect.bottom() - 10.0;
+        let mut shapes = vec![epaint::Shape::line_segment(
+            [epaint::pos2(rect.left(), table), epaint::pos2(rect.right(), table)],
+            epaint::Stroke::new(2.0, epaint::Color32::DARK_GRAY),
+        )];
+
+        let base = height_floor(scene.list.iter().flatten().map(Element::height));
+        let top = scene.list
+            .iter()
+            .flatten()
+            .zip(lifts)
+            .map(|(element, lift)| element.height() - base + lift)
+            .chain(hand)
+            .fold(1.0, f32::max);
+        let scale = (table - rect.top() - 25.0) / top;
+
+        // Leave a gap between the standing rods and the ones taken out
+        let slots = scene.list.iter().map(Vec::len).sum::<usize>() + scene.list.len().saturating_sub(1);
+        let slot_width = rect.width() / slots.max(1) as f32;
+        let mut positions = vec![];
+        let mut slot = 0;
+        for (list_index, list) in scene.list.iter().enumerate() {
+            for index in 0..list.len() {
+                positions.push(((list_index, index), slot));
+                slot += 1;
+            }
+            slot += 1;
+        }
+
+        for (((position, slot), element), lift) in positions.into_iter().zip(scene.list.iter().flatten()).zip(lifts) {
+            let color = if scene.flashing_sorted {
+                epaint::Color32::LIGHT_GREEN
+            } else if let Some(highlight) = scene.highlight(position) {
+                highlight.color()
+            } else {
+                SPAGHETTI
+            };
+
+            let left = (slot as f32).mul_add(slot_width, rect.left());
+            shapes.push(epaint::Shape::rect_filled(
+                egui::Rect::from_x_y_ranges(
+                    slot_width.mul_add(0.25, left)..=slot_width.mul_add(0.75, left),
+                    (element.height() - base + lift).mul_add(-scale, table)..=lift.mul_add(-scale, table),
+                ),
+                epaint::Rounding::ZERO,
+                color,
+            ));
+        }
+
+        if let Some(hand) = hand {
+            let width = scene.list.first().map_or(0, Vec::len) as f32 * slot_width;
+            let y = hand.mul_add(-scale, table);
+
+            shapes.push(epaint::Shape::rect_filled(
+                egui::Rect::from_x_y_ranges(rect.left()..=rect.left() + width, y - 4.0..=y),
+                epaint::Rounding::same(2.0),
+                epaint::Color32::LIGHT_GRAY,
+            ));
+        }
+
+        shapes
+    }
+}
@@ -0,0 +1,33 @@
+use super::{Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::items::Item;
+
+/// One dot per element, across by its index and up by its value. Sorted lists
+/// are a line going up from the bottom left.
+pub struct Scatter;
+
+impl<T: Item> Renderer<T> for Scatter {
+    fn get_name(&self) -> &'static str {
+        "Dots"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let hues = scene.hues();
+        let count = hues.len();
+        let dot_radius = (rect.width() / count.max(1) as f32 * 0.5).clamp(1.5, 6.0);
+        let area = rect.shrink(dot_radius + 10.0);
+
+        let mut shapes = vec![epaint::Shape::line_segment(
+            [area.left_bottom(), area.right_top()],
+            epaint::Stroke::new(1.0, epaint::Color32::DARK_GRAY),
+        )];
+        for ((index, fraction), color) in scene.fractions().into_iter().enumerate().zip(hues) {
+            let across = if count > 1 { index as f32 / (count - 1) as f32 } else { 0.5 };
+            let position = epaint::pos2(area.width().mul_add(across, area.left()), area.height().mul_add(-fraction, area.bottom()));
+            shapes.push(epaint::Shape::circle_filled(position, dot_radius, color));
+        }
+
+        shapes
+    }
+}
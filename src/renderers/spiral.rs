@@ -0,0 +1,34 @@
+use super::{point_around, Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::items::Item;
+
+/// One dot per element going around a circle by its index, further out the
+/// bigger its value. Sorted lists wind out in a spiral.
+pub struct Spiral;
+
+impl<T: Item> Renderer<T> for Spiral {
+    fn get_name(&self) -> &'static str {
+        "Spiral"
+    }
+
+    fn render(&self, scene: &Scene<'_, T>, rect: egui::Rect, _ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let hues = scene.hues();
+        let count = hues.len();
+        let center = rect.center();
+        let radius = rect.width().min(rect.height()) * 0.45;
+        let dot_radius = (std::f32::consts::PI * radius / count.max(1) as f32).clamp(1.5, 6.0);
+
+        // The smallest values are kept off the center so that they can still
+        // be told apart
+        scene.fractions()
+            .into_iter()
+            .enumerate()
+            .zip(hues)
+            .map(|((index, fraction), color)| {
+                let distance = radius * fraction.mul_add(0.9, 0.1);
+                epaint::Shape::circle_filled(point_around(center, index, count, distance), dot_radius, color)
+            })
+            .collect()
+    }
+}
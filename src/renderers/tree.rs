@@ -0,0 +1,108 @@
+use super::{Renderer, Scene};
+
+use eframe::egui::{self, epaint};
+use crate::{items::Item, sorting_algorithms::{height_floor, Highlight, Tree}};
+
+/// The binary tree that an algorithm builds beside its list, with each node's
+/// in-order position going across and its depth going down. It's drawn
+/// beside the list rather than in place of it.
+pub struct TreeDiagram<'a, T> {
+    pub tree: &'a Tree<T>,
+}
+
+impl<T: Item> Renderer<T> for TreeDiagram<'_, T> {
+    fn get_name(&self) -> &'static str {
+        "Tree"
+    }
+
+    fn render(&self, _scene: &Scene<'_, T>, rect: egui::Rect, ctx: &egui::Context) -> Vec<epaint::Shape> {
+        let tree = self.tree;
+        let mut shapes = vec![];
+        let Some(root) = tree.root else {
+            return shapes;
+        };
+
+        // Lay the nodes out with their in-order position going across and
+        // their depth going down
+        let mut positions = vec![(0, 0); tree.nodes.len()];
+        let mut stack = vec![];
+        let mut next = Some((root, 0));
+        let mut order = 0;
+        while next.is_some() || !stack.is_empty() {
+            while let Some((node, depth)) = next {
+                stack.push((node, depth));
+                next = tree.nodes[node].left.map(|left| (left, depth + 1));
+            }
+
+            let (node, depth) = stack.pop().unwrap();
+            positions[node] = (order, depth);
+            order += 1;
+            next = tree.nodes[node].right.map(|right| (right, depth + 1));
+        }
+
+        let depth = positions.iter().map(|(_, depth)| *depth).max().unwrap_or(0) + 1;
+        let spacing = egui::vec2(rect.width() / order as f32, rect.height() / depth as f32);
+        let radius = (spacing.min_elem() * 0.4).min(14.0);
+        let centers = positions
+            .iter()
+            .map(|(order, depth)| rect.left_top() + spacing * egui::vec2(*order as f32 + 0.5, *depth as f32 + 0.5))
+            .collect::<Vec<_>>();
+
+        for (node, center) in tree.nodes.iter().zip(&centers) {
+            for child in [node.left, node.right].into_iter().flatten() {
+                shapes.push(epaint::Shape::line_segment(
+                    [*center, centers[child]],
+                    epaint::Stroke::new(1.0, epaint::Color32::DARK_GRAY),
+                ));
+            }
+        }
+
+        let heights = tree.nodes.iter().flat_map(|node| node.values.iter().map(Item::height)).collect::<Vec<_>>();
+        let floor = height_floor(heights.iter().copied());
+        let top = heights.iter().copied().fold(floor, f32::max);
+        for (index, (node, center)) in tree.nodes.iter().zip(&centers).enumerate() {
+            let highlight = tree.highlights.iter().filter(|(node, _)| *node == index).map(|(_, highlight)| *highlight).max();
+            let plain = if node.values.is_empty() { epaint::Color32::DARK_GRAY } else { epaint::Color32::WHITE };
+            let color = highlight.map_or(plain, Highlight::color);
+
+            // Nodes holding a list are boxes with a little bar for each value
+            if node.values.len() > 1 {
+                let size = egui::vec2((spacing.x * 0.9).min(node.values.len() as f32 * 14.0), (spacing.y * 0.6).min(40.0));
+                let node_rect = egui::Rect::from_center_size(*center, size);
+                shapes.push(epaint::Shape::rect_filled(node_rect, epaint::Rounding::same(2.0), epaint::Color32::from_gray(40)));
+                shapes.push(epaint::Shape::rect_stroke(node_rect, epaint::Rounding::same(2.0), epaint::Stroke::new(1.0, color)));
+
+                let bar_width = (size.x - 4.0) / node.values.len() as f32;
+                for (slot, value) in node.values.iter().enumerate() {
+                    let fraction = (value.height() - floor) / (top - floor).max(f32::EPSILON);
+                    let left = (slot as f32).mul_add(bar_width, node_rect.left() + 2.0);
+                    shapes.push(epaint::Shape::rect_filled(
+                        egui::Rect::from_x_y_ranges(
+                            left..=left + (bar_width - 1.0).max(1.0),
+                            (size.y - 4.0).mul_add(-fraction, node_rect.bottom() - 2.0)..=node_rect.bottom() - 2.0,
+                        ),
+                        epaint::Rounding::ZERO,
+                        color,
+                    ));
+                }
+                continue;
+            }
+
+            shapes.push(epaint::Shape::circle_filled(*center, radius, color));
+
+            // Only label the nodes if the labels will fit
+            if let (Some(value), true) = (node.values.first(), radius >= 8.0) {
+                shapes.push(ctx.fonts(|fonts| epaint::Shape::text(
+                    fonts,
+                    *center,
+                    egui::Align2::CENTER_CENTER,
+                    value,
+                    egui::FontId::monospace(radius),
+                    epaint::Color32::BLACK,
+                )));
+            }
+        }
+
+        shapes
+    }
+}
@@ -0,0 +1,13 @@
+pub mod swap_shuffle;
+
+use crate::{items::Item, sorting_algorithms::SortingAlgorithm};
+
+/// Shuffles are stepped through just like sorting algorithms are, but their
+/// goal is `Goal::Shuffled`.
+pub fn get_available_shuffles<T: Item>() -> Vec<Box<dyn SortingAlgorithm<T>>> {
+    vec![
+        Box::new(swap_shuffle::SwapShuffle::fisher_yates()),
+        Box::new(swap_shuffle::SwapShuffle::naive()),
+        Box::new(swap_shuffle::SwapShuffle::sattolo()),
+    ]
+}
@@ -0,0 +1,160 @@
+use crate::sorting_algorithms::{Accesses, Element, Goal, Pseudocode, SortingAlgorithm};
+
+use std::{ops::Range, time};
+use rand::prelude::*;
+use crate::items::Item;
+
+/// Which elements a shuffle can swap the current one with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SwapRange {
+    /// Itself or anything after it, which makes every order equally likely.
+    FisherYates,
+    /// Anything at all. There are `n^n` ways for this to go, which can't be
+    /// split evenly between the `n!` orders, so some orders come up more.
+    Naive,
+    /// Anything after it but never itself, which only makes orders that are a
+    /// single cycle.
+    Sattolo,
+}
+
+const FISHER_YATES_PSEUDOCODE: &[&str] = &[
+    "fisher_yates(a):",
+    "  for i in 0..len(a):",
+    "    j = random index in i..len(a)",
+    "    swap a[i], a[j]",
+];
+
+const NAIVE_PSEUDOCODE: &[&str] = &[
+    "naive_shuffle(a):",
+    "  for i in 0..len(a):",
+    "    j = random index in 0..len(a)",
+    "    swap a[i], a[j]",
+];
+
+const SATTOLO_PSEUDOCODE: &[&str] = &[
+    "sattolo(a):",
+    "  for i in 0..len(a) - 1:",
+    "    j = random index in i + 1..len(a)",
+    "    swap a[i], a[j]",
+];
+
+/// A shuffle that walks through the list swapping each element with a random
+/// one.
+#[derive(Clone)]
+pub struct SwapShuffle<T> {
+    // Statics, defaulted by the shuffle and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+    swap_range: SwapRange,
+
+    // Mutables, these change as the shuffle works.
+    list: Vec<Element<T>>,
+    index: usize,
+    // The last swap that was made
+    last_swap: Option<(usize, usize)>,
+    rng: StdRng,
+    accesses: Accesses,
+}
+
+impl<T: Item> SwapShuffle<T> {
+    pub fn fisher_yates() -> Self {
+        Self::new("Fisher-Yates Shuffle", SwapRange::FisherYates)
+    }
+
+    pub fn naive() -> Self {
+        Self::new("Naive Shuffle", SwapRange::Naive)
+    }
+
+    pub fn sattolo() -> Self {
+        Self::new("Sattolo's Cycle", SwapRange::Sattolo)
+    }
+
+    fn new(name: &'static str, swap_range: SwapRange) -> Self {
+        Self {
+            name,
+            default_delay: time::Duration::from_millis(100),
+            swap_range,
+            list: Element::numbered(16),
+            index: 0,
+            last_swap: None,
+            rng: StdRng::seed_from_u64(0),
+            accesses: Accesses::default(),
+        }
+    }
+
+    const fn swap_range(&self) -> Range<usize> {
+        match self.swap_range {
+            SwapRange::FisherYates => self.index..self.list.len(),
+            SwapRange::Naive => 0..self.list.len(),
+            SwapRange::Sattolo => self.index + 1..self.list.len(),
+        }
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for SwapShuffle<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let highlights = self.last_swap
+            .map(|(first, second)| vec![(0, first), (0, second)])
+            .unwrap_or_default();
+
+        (vec![self.list.clone()], highlights)
+    }
+
+    fn get_goal(&self) -> Goal {
+        Goal::Shuffled
+    }
+
+    fn is_finished(&self) -> bool {
+        self.swap_range().is_empty() || self.index >= self.list.len()
+    }
+
+    // Picking and swapping happen in one step, so the line is the pick
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let lines = match self.swap_range {
+            SwapRange::FisherYates => FISHER_YATES_PSEUDOCODE,
+            SwapRange::Naive => NAIVE_PSEUDOCODE,
+            SwapRange::Sattolo => SATTOLO_PSEUDOCODE,
+        };
+        let mut variables = vec![("i", self.index.to_string())];
+        if let Some((_, other)) = self.last_swap {
+            variables.push(("last j", other.to_string()));
+        }
+
+        Some(Pseudocode { lines, line: (!self.is_finished()).then_some(2), variables })
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.list = list.into_iter().flatten().collect();
+        self.index = 0;
+        self.last_swap = None;
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        if self.is_finished() {
+            return;
+        }
+
+        let other = self.rng.gen_range(self.swap_range());
+        self.list.swap(self.index, other);
+        self.accesses.swap((0, self.index), (0, other));
+        self.last_swap = Some((self.index, other));
+        self.index += 1;
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
@@ -0,0 +1,178 @@
+use super::{Accesses, Element, Layout, Pseudocode, SortingAlgorithm};
+
+use std::time;
+use crate::order::{Key, Order};
+
+/// The most places for beads the abacus can have, counting a place for each
+/// rod on each row, so that big values can't run out of memory.
+const MAX_PLACES: usize = 10_000_000;
+
+const PSEUDOCODE: &[&str] = &[
+    "bead_sort(a):",
+    "  thread a[i] beads onto row i for each i",
+    "  while any bead has a gap under it:",
+    "    drop every such bead by one row",
+];
+
+/// Gravity sort. Each element is a row of beads on an abacus, and the beads
+/// all fall down their rods. Once they've settled, the number of beads in
+/// each row is the sorted list.
+#[derive(Clone)]
+pub struct BeadSort {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    // The rows of the abacus, with the top row first
+    grid: Vec<Vec<bool>>,
+    // Descending orders have the beads fall up
+    order: Order,
+    accesses: Accesses,
+}
+
+impl BeadSort {
+    /// How many beads have a gap under them to fall into. Falling up is
+    /// falling down with the abacus turned over.
+    fn falling(&self) -> usize {
+        self.grid
+            .windows(2)
+            .map(|rows| {
+                let (above, below) = if self.order.descending { (&rows[1], &rows[0]) } else { (&rows[0], &rows[1]) };
+                above.iter().zip(below).filter(|(above, below)| **above && !**below).count()
+            })
+            .sum()
+    }
+}
+
+impl Default for BeadSort {
+    fn default() -> Self {
+        let mut bead_sort = Self {
+            name: "Bead Sort",
+            default_delay: time::Duration::from_millis(100),
+            grid: vec![],
+            order: Order::default(),
+            accesses: Accesses::default(),
+        };
+        bead_sort.set_list(vec![Element::numbered(16)]);
+
+        bead_sort
+    }
+}
+
+impl SortingAlgorithm<usize> for BeadSort {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<usize>>>, Vec<(usize, usize)>) {
+        // The beads don't remember which row they started in, so the rows are
+        // tagged by where they are
+        let list = Element::tagged(self.grid.iter().map(|row| row.iter().filter(|bead| **bead).count()))
+            .into_iter()
+            .map(|element| element.with_order(self.order))
+            .collect();
+
+        (vec![list], vec![])
+    }
+
+    fn get_layout(&self) -> Layout {
+        Layout::Beads(self.grid.clone())
+    }
+
+    // The rows are made anew out of how many beads they hold
+    fn moves_elements(&self) -> bool {
+        false
+    }
+
+    // The beads only count the values, so they can't carry the values along
+    // with other keys
+    fn supports_order(&self, order: Order) -> bool {
+        order.key == Key::Value
+    }
+
+    fn check_list(&self, list: &[Vec<Element<usize>>]) -> Result<(), String> {
+        let length = list.iter().map(Vec::len).sum::<usize>();
+        let rods = list.iter().flatten().map(|element| element.value).max().unwrap_or(0);
+
+        match length.checked_mul(rods) {
+            Some(places) if places <= MAX_PLACES => Ok(()),
+            _ => Err(format!(
+                "{} would need {length} rows of {rods} rods for this list, which is more than the {MAX_PLACES} places its abacus can have",
+                self.name,
+            )),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.falling() == 0
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let falling = self.falling();
+        let variables = vec![
+            ("rows", self.grid.len().to_string()),
+            ("rods", self.grid.first().map_or(0, Vec::len).to_string()),
+            ("falling", falling.to_string()),
+        ];
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: (falling > 0).then_some(3), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<usize>>>) {
+        let list = list.into_iter().flatten().collect::<Vec<_>>();
+        self.order = list.first().map_or_else(Order::default, |element| element.order);
+        let list = list.into_iter().map(|element| element.value).collect::<Vec<_>>();
+        let rods = list.iter().max().copied().unwrap_or(0);
+
+        self.grid = list
+            .into_iter()
+            .map(|value| (0..rods).map(|rod| rod < value).collect())
+            .collect();
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    // Each row is an element, and looking for a gap under a bead compares the
+    // rows at that rod
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+
+        // Falling up is falling down with the abacus turned over
+        if self.order.descending {
+            self.grid.reverse();
+        }
+        let rows = self.grid.len();
+        let descending = self.order.descending;
+        let shown = |row: usize| (0, if descending { rows - 1 - row } else { row });
+
+        // Every bead with a gap under it falls by one row. Going from the
+        // bottom up stops a bead from falling twice in one step.
+        for row in (1..rows).rev() {
+            let mut fell = false;
+            for rod in 0..self.grid[row].len() {
+                if self.grid[row - 1][rod] && !self.grid[row][rod] {
+                    self.grid[row - 1][rod] = false;
+                    self.grid[row][rod] = true;
+                    fell = true;
+                }
+            }
+
+            self.accesses.reads.extend([shown(row - 1), shown(row)]);
+            self.accesses.comparisons += self.grid[row].len();
+            if fell {
+                self.accesses.writes.extend([shown(row - 1), shown(row)]);
+            }
+        }
+
+        if self.order.descending {
+            self.grid.reverse();
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
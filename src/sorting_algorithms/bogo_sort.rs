@@ -1,45 +1,87 @@
-use super::SortingAlgorithm;
+use super::{Accesses, Element, Pseudocode, SortingAlgorithm};
 
 use std::time;
 use rand::prelude::*;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "bogo_sort(a):",
+    "  while a isn't sorted:",
+    "    shuffle(a)",
+];
 
 #[derive(Clone)]
-pub struct BogoSort {
+pub struct BogoSort<T> {
     // Statics, defaulted by the sorting algorithm and kept that way
     name: &'static str,
     default_delay: time::Duration,
 
     // Mutables, these change as the sorting algorithm works.
-    current_list: Vec<Vec<usize>>,
-    rng: rand::rngs::ThreadRng,
+    current_list: Vec<Vec<Element<T>>>,
+    rng: StdRng,
+    accesses: Accesses,
 }
 
-impl Default for BogoSort {
+impl<T: Item> Default for BogoSort<T> {
     fn default() -> Self {
         Self {
             name: "Bogo Sort",
             default_delay: time::Duration::from_millis(40),
-            current_list: vec![(1..=4).collect()],
-            rng: rand::thread_rng(),
+            current_list: vec![Element::numbered(4)],
+            rng: StdRng::seed_from_u64(0),
+            accesses: Accesses::default(),
         }
     }
 }
 
-impl SortingAlgorithm for BogoSort {
+impl<T: Item> SortingAlgorithm<T> for BogoSort<T> {
     fn get_name(&self) -> &str {
         self.name
     }
 
-    fn get_list(&self) -> (Vec<Vec<usize>>, Vec<(usize, usize)>) {
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
         (self.current_list.clone(), vec![])
     }
 
-    fn set_list(&mut self, list: Vec<Vec<usize>>) {
+    fn is_finished(&self) -> bool {
+        self.current_list.iter().all(|list| list.windows(2).all(|pair| pair[0] <= pair[1]))
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let sorted = self.current_list.iter().all(|list| list.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: (!sorted).then_some(2), variables: vec![] })
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
         self.current_list = list;
     }
 
+    // The list is checked before it's shuffled, going until a pair is out of
+    // order, so that a sorted list is left alone
     fn step(&mut self) {
-        self.current_list.get_mut(0).unwrap().shuffle(&mut self.rng);
+        self.accesses = Accesses::default();
+        let Some(list) = self.current_list.get_mut(0) else {
+            return;
+        };
+
+        for index in 1..list.len() {
+            self.accesses.compare((0, index - 1), (0, index));
+            if list[index - 1] > list[index] {
+                list.shuffle(&mut self.rng);
+                self.accesses.reads.extend((0..list.len()).map(|index| (0, index)));
+                self.accesses.writes.extend((0..list.len()).map(|index| (0, index)));
+                return;
+            }
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
     }
 
     fn get_delay(&self) -> std::time::Duration {
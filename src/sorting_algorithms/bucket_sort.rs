@@ -0,0 +1,271 @@
+use super::{
+    inner_sort::{InnerSort, InnerSorter},
+    Accesses,
+    Element,
+    Pseudocode,
+    SortingAlgorithm,
+};
+
+use std::time;
+use eframe::egui;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "bucket_sort(a, buckets):",
+    "  for i in 0..len(a):",
+    "    b[i] = (a[i] - min) / (max - min) * buckets",
+    "  for each x in a:",
+    "    append x to bucket b[x]",
+    "  for each bucket:",
+    "    sort(bucket)",
+    "  a = the buckets joined together",
+];
+
+#[derive(Clone)]
+pub struct BucketSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Settings, changed from the settings panel
+    bucket_count: usize,
+    inner_sort: InnerSort,
+
+    // Mutables, these change as the sorting algorithm works.
+    input: Vec<Element<T>>,
+    buckets: Vec<Vec<Element<T>>>,
+    range: (f32, f32),
+    phase: Phase,
+    accesses: Accesses,
+}
+
+#[derive(Clone, Debug)]
+enum Phase {
+    /// Working out which bucket each element goes in, up to the given index.
+    Classify(usize),
+    /// Moving the elements from the input into their buckets.
+    Scatter,
+    /// Sorting the given bucket.
+    Sort(usize, InnerSorter),
+    Done,
+}
+
+impl<T: Item> BucketSort<T> {
+    fn restart(&mut self, list: Vec<Element<T>>) {
+        let heights = list.iter().map(Element::height);
+        let min = heights.clone().fold(f32::INFINITY, f32::min);
+        let max = heights.fold(f32::NEG_INFINITY, f32::max);
+
+        self.range = (min, max);
+        self.buckets = vec![vec![]; self.bucket_count];
+        self.phase = if list.is_empty() { Phase::Done } else { Phase::Classify(0) };
+        self.input = list;
+    }
+
+    fn bucket_of(&self, height: f32) -> usize {
+        let (min, max) = self.range;
+        if min >= max {
+            return 0;
+        }
+
+        let bucket = (height - min) / (max - min) * self.bucket_count as f32;
+        (bucket as usize).min(self.bucket_count - 1)
+    }
+}
+
+impl<T: Item> Default for BucketSort<T> {
+    fn default() -> Self {
+        let mut bucket_sort = Self {
+            name: "Bucket Sort",
+            default_delay: time::Duration::from_millis(60),
+            bucket_count: 5,
+            inner_sort: InnerSort::Insertion,
+            input: vec![],
+            buckets: vec![],
+            range: (0.0, 0.0),
+            phase: Phase::Done,
+            accesses: Accesses::default(),
+        };
+        bucket_sort.restart(Element::numbered(32));
+
+        bucket_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for BucketSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        match &self.phase {
+            Phase::Classify(index) => (vec![self.input.clone()], vec![(0, *index)]),
+            Phase::Scatter => {
+                let mut lists = self.buckets.clone();
+                lists.push(self.input.clone());
+
+                (lists, vec![(self.bucket_count, 0)])
+            },
+            Phase::Sort(bucket, sorter) => {
+                let highlights = sorter
+                    .get_highlights(&self.buckets[*bucket])
+                    .into_iter()
+                    .map(|index| (*bucket, index))
+                    .collect();
+
+                (self.buckets.clone(), highlights)
+            },
+            Phase::Done => (vec![self.input.clone()], vec![]),
+        }
+    }
+
+    fn get_groups(&self) -> Vec<((usize, usize), usize)> {
+        let (lists, _) = self.get_list();
+        let classified = match self.phase {
+            Phase::Classify(index) => index,
+            _ => usize::MAX,
+        };
+
+        lists
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| {
+                list
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, element)| ((list_index, index), element.height()))
+            })
+            .take(classified)
+            .map(|(position, height)| (position, self.bucket_of(height)))
+            .collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Done)
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let (line, variables) = match &self.phase {
+            Phase::Classify(index) => {
+                let bucket = self.input.get(*index).map(|element| self.bucket_of(element.height()));
+                (2, vec![("i", index.to_string()), ("b[i]", bucket.map_or_else(String::new, |bucket| bucket.to_string()))])
+            },
+            Phase::Scatter => {
+                let element = &self.input[0];
+                (4, vec![("x", element.value.to_string()), ("b[x]", self.bucket_of(element.height()).to_string())])
+            },
+            Phase::Sort(bucket, sorter) => {
+                let mut variables = vec![("bucket", bucket.to_string())];
+                variables.extend(sorter.get_variables());
+                (6, variables)
+            },
+            Phase::Done => return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] }),
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.restart(list.into_iter().flatten().collect());
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    // The input is shown after the buckets while it's scattered, and the
+    // bucket of each element is only worked out once
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        match &mut self.phase {
+            Phase::Classify(index) => {
+                self.accesses.compare_aside((0, *index));
+                *index += 1;
+
+                if *index == self.input.len() {
+                    self.phase = Phase::Scatter;
+                }
+            },
+            Phase::Scatter => {
+                let element = self.input.remove(0);
+                let bucket = self.bucket_of(element.height());
+                self.buckets[bucket].push(element);
+                self.accesses.read((self.bucket_count, 0));
+                self.accesses.write((bucket, self.buckets[bucket].len() - 1));
+
+                if self.input.is_empty() {
+                    self.phase = Phase::Sort(0, InnerSorter::new(self.inner_sort, self.buckets[0].len()));
+                }
+            },
+            Phase::Sort(bucket, sorter) => {
+                sorter.step(&mut self.buckets[*bucket], &mut self.accesses, (*bucket, 0));
+
+                // Skip past the finished (and empty) buckets
+                while sorter.is_finished(&self.buckets[*bucket]) {
+                    *bucket += 1;
+
+                    if *bucket == self.bucket_count {
+                        // Joining the buckets back together copies every
+                        // element into the list, which is where anything
+                        // written to the buckets this step ends up
+                        let starts = self.buckets
+                            .iter()
+                            .scan(0, |start, list| {
+                                *start += list.len();
+                                Some(*start - list.len())
+                            })
+                            .collect::<Vec<_>>();
+                        for write in &mut self.accesses.writes {
+                            *write = (0, starts[write.0] + write.1);
+                        }
+                        self.accesses.reads.extend(self.buckets
+                            .iter()
+                            .enumerate()
+                            .flat_map(|(bucket, list)| (0..list.len()).map(move |index| (bucket, index))));
+                        self.input = self.buckets.concat();
+                        self.accesses.writes.extend((0..self.input.len()).map(|index| (0, index)));
+                        self.buckets = vec![vec![]; self.bucket_count];
+                        self.phase = Phase::Done;
+                        return;
+                    }
+                    *sorter = InnerSorter::new(self.inner_sort, self.buckets[*bucket].len());
+                }
+            },
+            Phase::Done => {},
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+
+    fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        let mut bucket_count = self.bucket_count;
+        let mut inner_sort = self.inner_sort;
+
+        ui.horizontal(|ui| {
+            ui.label("Buckets:");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                ui.add(egui::DragValue::new(&mut bucket_count).speed(0.05).range(1..=64));
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Bucket sort:");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                egui::ComboBox::from_id_source("bucket inner sort")
+                    .selected_text(inner_sort.get_name())
+                    .show_ui(ui, |ui| {
+                        for sort in InnerSort::ALL {
+                            ui.selectable_value(&mut inner_sort, sort, sort.get_name());
+                        }
+                    });
+            });
+        });
+
+        if bucket_count != self.bucket_count || inner_sort != self.inner_sort {
+            self.bucket_count = bucket_count;
+            self.inner_sort = inner_sort;
+            self.set_list(self.get_list().0);
+        }
+    }
+}
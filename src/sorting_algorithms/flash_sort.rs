@@ -0,0 +1,262 @@
+use super::{
+    inner_sort::{InnerSort, InnerSorter},
+    Accesses,
+    Element,
+    Pseudocode,
+    SortingAlgorithm,
+};
+
+use std::time;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "flash_sort(a):",
+    "  for i in 0..len(a):",
+    "    count[class(a[i])] += 1",
+    "  turn the counts into where each class ends",
+    "  while fewer than len(a) - 1 elements have moved:",
+    "    if no cycle is going: flash = a[leader]",
+    "    end[class(flash)] -= 1",
+    "    swap flash, a[end[class(flash)]]",
+    "  insertion_sort(a)",
+];
+
+/// Neubert's flashsort: the elements are classified into `0.43 * n` classes
+/// by value, permuted into their class's region in cycles, and the nearly
+/// sorted result is finished off with an insertion sort.
+#[derive(Clone)]
+pub struct FlashSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<T>>,
+    classes: Classes,
+    // The number of elements in each class and the ones below it. During the
+    // permutation these count down to where the next element of the class
+    // goes.
+    class_ends: Vec<usize>,
+    phase: Phase<T>,
+    accesses: Accesses,
+}
+
+#[derive(Clone, Debug)]
+enum Phase<T> {
+    /// Counting the class of each element, up to the given index.
+    Classify(usize),
+    /// Moving elements into their class's region.
+    Permute(Permutation<T>),
+    /// Finishing off with an insertion sort.
+    Insert(InnerSorter),
+    Done,
+}
+
+/// Maps heights linearly onto classes.
+#[derive(Clone, Copy, Debug)]
+struct Classes {
+    min: f32,
+    max: f32,
+    count: usize,
+}
+
+impl Classes {
+    fn class_of<T: Item>(self, element: &Element<T>) -> usize {
+        if self.min >= self.max {
+            return 0;
+        }
+
+        let class = (self.count - 1) as f32 * (element.height() - self.min) / (self.max - self.min);
+        (class as usize).min(self.count - 1)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Permutation<T> {
+    // The start of the current cycle
+    leader: usize,
+    // The class of the last element that was moved
+    class: usize,
+    // The element that is being carried around the cycle, if a cycle is going
+    flash: Option<Element<T>>,
+    // Where the last element was put
+    last_write: usize,
+    moves: usize,
+}
+
+impl<T: Item> FlashSort<T> {
+    fn restart(&mut self, mut list: Vec<Element<T>>) {
+        let heights = list.iter().map(Element::height);
+        let min = heights.clone().fold(f32::INFINITY, f32::min);
+        let max = heights.fold(f32::NEG_INFINITY, f32::max);
+
+        // Neubert starts with the largest element at the front so that the
+        // first cycle has somewhere to start
+        if let Some((max_index, _)) = list.iter().enumerate().max_by(|(_, a), (_, b)| a.cmp(b)) {
+            list.swap(0, max_index);
+        }
+
+        self.classes = Classes {
+            min,
+            max,
+            count: ((list.len() as f64 * 0.43) as usize).max(1),
+        };
+        self.class_ends = vec![0; self.classes.count];
+        self.phase = if list.len() < 2 { Phase::Done } else { Phase::Classify(0) };
+        self.list = list;
+    }
+}
+
+impl<T: Item> Default for FlashSort<T> {
+    fn default() -> Self {
+        let mut flash_sort = Self {
+            name: "Flashsort",
+            default_delay: time::Duration::from_millis(60),
+            list: vec![],
+            classes: Classes { min: 0.0, max: 0.0, count: 1 },
+            class_ends: vec![],
+            phase: Phase::Done,
+            accesses: Accesses::default(),
+        };
+        flash_sort.restart(Element::numbered(32));
+
+        flash_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for FlashSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let highlights = match &self.phase {
+            Phase::Classify(index) => vec![*index],
+            Phase::Permute(permutation) => vec![permutation.leader, permutation.last_write],
+            Phase::Insert(sorter) => sorter.get_highlights(&self.list),
+            Phase::Done => vec![],
+        };
+
+        (vec![self.list.clone()], highlights.into_iter().map(|index| (0, index)).collect())
+    }
+
+    fn get_groups(&self) -> Vec<((usize, usize), usize)> {
+        let classes = self.classes;
+        let classified = match self.phase {
+            Phase::Classify(index) => index,
+            _ => self.list.len(),
+        };
+
+        self.list[..classified]
+            .iter()
+            .enumerate()
+            .map(|(index, element)| ((0, index), classes.class_of(element)))
+            .collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Done)
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let (line, variables) = match &self.phase {
+            Phase::Classify(index) => {
+                let class = self.classes.class_of(&self.list[*index]);
+                (2, vec![("i", index.to_string()), ("class(a[i])", class.to_string())])
+            },
+            Phase::Permute(permutation) => {
+                let mut variables = vec![("leader", permutation.leader.to_string()), ("moved", permutation.moves.to_string())];
+                if let Some(flash) = &permutation.flash {
+                    variables.push(("flash", flash.value.to_string()));
+                    variables.push(("class(flash)", self.classes.class_of(flash).to_string()));
+                }
+                (if permutation.flash.is_some() { 7 } else { 5 }, variables)
+            },
+            Phase::Insert(sorter) => (8, sorter.get_variables()),
+            Phase::Done => return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] }),
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.restart(list.into_iter().flatten().collect());
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let classes = self.classes;
+        match &mut self.phase {
+            Phase::Classify(index) => {
+                self.accesses.compare_aside((0, *index));
+                let class = classes.class_of(&self.list[*index]);
+                self.class_ends[class] += 1;
+                *index += 1;
+
+                if *index == self.list.len() {
+                    for class in 1..classes.count {
+                        self.class_ends[class] += self.class_ends[class - 1];
+                    }
+
+                    self.phase = Phase::Permute(Permutation {
+                        leader: 0,
+                        class: classes.count - 1,
+                        flash: None,
+                        last_write: 0,
+                        moves: 0,
+                    });
+                }
+            },
+            Phase::Permute(permutation) => {
+                let flash = if let Some(flash) = permutation.flash.take() {
+                    flash
+                } else {
+                    // Find the next element that hasn't been moved yet to
+                    // start a new cycle from
+                    while permutation.leader >= self.class_ends[permutation.class] {
+                        permutation.leader += 1;
+                        permutation.class = classes.class_of(&self.list[permutation.leader]);
+                        self.accesses.compare_aside((0, permutation.leader));
+                    }
+
+                    self.accesses.read((0, permutation.leader));
+                    self.list[permutation.leader].clone()
+                };
+
+                // The element being carried is held aside, so working out its
+                // class doesn't read the list
+                permutation.class = classes.class_of(&flash);
+                self.accesses.comparisons += 1;
+                self.class_ends[permutation.class] -= 1;
+                permutation.last_write = self.class_ends[permutation.class];
+                let held = std::mem::replace(&mut self.list[permutation.last_write], flash);
+                self.accesses.read((0, permutation.last_write));
+                self.accesses.write((0, permutation.last_write));
+                permutation.moves += 1;
+
+                // The cycle is over once it gets back around to the leader
+                permutation.flash = if permutation.last_write == permutation.leader { None } else { Some(held) };
+
+                if permutation.flash.is_none() && permutation.moves + 1 >= self.list.len() {
+                    self.phase = Phase::Insert(InnerSorter::new(InnerSort::Insertion, self.list.len()));
+                }
+            },
+            Phase::Insert(sorter) => {
+                sorter.step(&mut self.list, &mut self.accesses, (0, 0));
+
+                if sorter.is_finished(&self.list) {
+                    self.phase = Phase::Done;
+                }
+            },
+            Phase::Done => {},
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
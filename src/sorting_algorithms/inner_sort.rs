@@ -0,0 +1,172 @@
+//! Small in-place sorts that other sorting algorithms can run one step at a
+//! time over part of their list (e.g. the buckets of a bucket sort).
+
+use super::Accesses;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InnerSort {
+    Insertion,
+    Selection,
+    Bubble,
+}
+
+impl InnerSort {
+    pub const ALL: [Self; 3] = [Self::Insertion, Self::Selection, Self::Bubble];
+
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Insertion => "Insertion Sort",
+            Self::Selection => "Selection Sort",
+            Self::Bubble => "Bubble Sort",
+        }
+    }
+}
+
+/// The progress of an `InnerSort` over a list.
+#[derive(Clone, Debug)]
+pub enum InnerSorter {
+    /// The index being inserted and where it currently is.
+    Insertion(usize, usize),
+    /// The index being filled, the index being compared and the smallest
+    /// index found so far.
+    Selection(usize, usize, usize),
+    /// The end of the unsorted part, the index being compared and whether the
+    /// current pass swapped anything.
+    Bubble(usize, usize, bool),
+}
+
+impl InnerSorter {
+    pub const fn new(sort: InnerSort, length: usize) -> Self {
+        match sort {
+            InnerSort::Insertion => Self::Insertion(1, 1),
+            InnerSort::Selection => Self::Selection(0, 1, 0),
+            InnerSort::Bubble => Self::Bubble(length, 0, false),
+        }
+    }
+
+    /// Whether the list has been fully sorted.
+    pub const fn is_finished<T>(&self, list: &[T]) -> bool {
+        match *self {
+            Self::Insertion(index, _) => index >= list.len(),
+            Self::Selection(index, ..) => index + 1 >= list.len(),
+            Self::Bubble(end, ..) => end <= 1,
+        }
+    }
+
+    /// The indexes that are being looked at.
+    pub fn get_highlights<T>(&self, list: &[T]) -> Vec<usize> {
+        if self.is_finished(list) {
+            return vec![];
+        }
+
+        match *self {
+            Self::Insertion(_, position) => vec![position - 1, position],
+            Self::Selection(_, compared, smallest) => vec![smallest, compared],
+            Self::Bubble(_, compared, _) => vec![compared, compared + 1],
+        }
+    }
+
+    /// The sort's loop variables and their values, for pseudocode.
+    pub fn get_variables(&self) -> Vec<(&'static str, String)> {
+        match *self {
+            Self::Insertion(index, position) => vec![("i", index.to_string()), ("j", position.to_string())],
+            Self::Selection(index, compared, smallest) => vec![
+                ("i", index.to_string()),
+                ("j", compared.to_string()),
+                ("smallest", smallest.to_string()),
+            ],
+            Self::Bubble(end, compared, swapped) => vec![
+                ("end", end.to_string()),
+                ("j", compared.to_string()),
+                ("swapped", swapped.to_string()),
+            ],
+        }
+    }
+
+    /// Do one comparison (and maybe a swap), counting the accesses. The list
+    /// starts at the position in the lists the sorting algorithm shows.
+    pub fn step<T: Ord>(&mut self, list: &mut [T], accesses: &mut Accesses, start: (usize, usize)) {
+        if self.is_finished(list) {
+            return;
+        }
+
+        let at = |index: usize| (start.0, start.1 + index);
+        match self {
+            // The element is inserted once it's no smaller than the one before
+            // it, which the next step finds out, or it has reached the front
+            Self::Insertion(index, position) => {
+                accesses.compare(at(*position - 1), at(*position));
+                let inserted = if list[*position - 1] > list[*position] {
+                    list.swap(*position - 1, *position);
+                    accesses.swap(at(*position - 1), at(*position));
+                    *position -= 1;
+                    *position == 0
+                } else {
+                    true
+                };
+
+                if inserted {
+                    *index += 1;
+                    *position = *index;
+                }
+            },
+            Self::Selection(index, compared, smallest) => {
+                accesses.compare(at(*compared), at(*smallest));
+                if list[*compared] < list[*smallest] {
+                    *smallest = *compared;
+                }
+                *compared += 1;
+
+                if *compared == list.len() {
+                    list.swap(*index, *smallest);
+                    accesses.swap(at(*index), at(*smallest));
+                    *index += 1;
+                    *compared = *index + 1;
+                    *smallest = *index;
+                }
+            },
+            Self::Bubble(end, compared, swapped) => {
+                accesses.compare(at(*compared), at(*compared + 1));
+                if list[*compared] > list[*compared + 1] {
+                    list.swap(*compared, *compared + 1);
+                    accesses.swap(at(*compared), at(*compared + 1));
+                    *swapped = true;
+                }
+                *compared += 1;
+
+                if *compared + 1 >= *end {
+                    *end = if *swapped { *end - 1 } else { 0 };
+                    *compared = 0;
+                    *swapped = false;
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the sort over the list, giving back the comparisons and writes it
+    /// made.
+    fn run(sort: InnerSort, list: &mut [usize]) -> (usize, usize) {
+        let mut sorter = InnerSorter::new(sort, list.len());
+        let mut accesses = Accesses::default();
+        while !sorter.is_finished(list) {
+            sorter.step(list, &mut accesses, (0, 0));
+        }
+
+        (accesses.comparisons, accesses.write_count())
+    }
+
+    #[test]
+    fn insertion_counts_each_comparison_once() {
+        // Each element is compared until it finds its place, and each place
+        // it moves past swaps two elements
+        let mut list = [5, 2, 4, 6, 1, 3];
+
+        assert_eq!(run(InnerSort::Insertion, &mut list), (12, 18));
+        assert_eq!(list, [1, 2, 3, 4, 5, 6]);
+    }
+}
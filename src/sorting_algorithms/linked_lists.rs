@@ -0,0 +1,440 @@
+use super::{Accesses, Element, HighlightedList, Layout, Pseudocode, SortingAlgorithm};
+
+use std::time;
+use crate::items::Item;
+
+/// Nodes that stay where they are in memory, linked up by their next
+/// pointers. Sorting them only ever rewires the pointers, and never copies an
+/// element.
+#[derive(Clone, Debug)]
+struct Nodes<T> {
+    elements: Vec<Element<T>>,
+    next: Vec<Option<usize>>,
+}
+
+impl<T: Item> Nodes<T> {
+    /// The elements laid out one after another, each linked to the next.
+    fn linked(list: Vec<Vec<Element<T>>>) -> Self {
+        let elements = list.into_iter().flatten().collect::<Vec<_>>();
+        let next = (1..=elements.len()).map(|node| (node < elements.len()).then_some(node)).collect();
+
+        Self { elements, next }
+    }
+
+    /// The nodes of the chain from the head, up to the last node if it's
+    /// given, or up to the end of the chain otherwise.
+    fn chain(&self, head: Option<usize>, last: Option<usize>) -> Vec<usize> {
+        let mut chain = vec![];
+        let mut node = head;
+        while let Some(current) = node {
+            chain.push(current);
+            if Some(current) == last {
+                break;
+            }
+            node = self.next[current];
+        }
+
+        chain
+    }
+
+    /// The value of the node for pseudocode, or "none" for no node.
+    fn describe(&self, node: Option<usize>) -> String {
+        node.map_or_else(|| "none".to_string(), |node| self.elements[node].value.to_string())
+    }
+
+    /// The chains as lists, with the given nodes highlighted.
+    fn get_list(&self, chains: &[Vec<usize>], highlighted: &[usize]) -> HighlightedList<T> {
+        let lists = chains
+            .iter()
+            .map(|chain| chain.iter().map(|node| self.elements[*node].clone()).collect())
+            .collect();
+
+        (lists, Self::positions(chains, highlighted))
+    }
+
+    /// Where the nodes are in the chains, leaving out any that aren't in one.
+    fn positions(chains: &[Vec<usize>], nodes: &[usize]) -> Vec<(usize, usize)> {
+        nodes
+            .iter()
+            .filter_map(|node| {
+                chains.iter().enumerate().find_map(|(list_index, chain)| {
+                    chain.iter().position(|other| other == node).map(|index| (list_index, index))
+                })
+            })
+            .collect()
+    }
+}
+
+const INSERTION_PSEUDOCODE: &[&str] = &[
+    "insertion_sort(unsorted):",
+    "  while unsorted isn't empty:",
+    "    node = unlink the head of unsorted",
+    "    previous = none; current = sorted",
+    "    while current <= node:",
+    "      previous = current; current = current.next",
+    "    link node in between previous and current",
+];
+
+const MERGE_PSEUDOCODE: &[&str] = &[
+    "natural_merge_sort(list):",
+    "  runs = cut list wherever it goes down",
+    "  while there's more than one run:",
+    "    left, right = the next two runs",
+    "    while both runs have nodes:",
+    "      link on the smaller head, left on ties",
+    "    link on the rest of the other run",
+];
+
+/// Insertion sort on a linked list. Each node is unlinked from the front of
+/// the unsorted list, then walked along the sorted list until the place it
+/// goes is found, and linked in there.
+#[derive(Clone)]
+pub struct LinkedInsertionSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    nodes: Nodes<T>,
+    sorted: Option<usize>,
+    unsorted: Option<usize>,
+    insertion: Option<Insertion>,
+    accesses: Accesses,
+}
+
+/// A node being inserted into the sorted list, with the node it'll be linked
+/// in after and the node it's being compared against.
+#[derive(Clone, Copy, Debug)]
+struct Insertion {
+    node: usize,
+    previous: Option<usize>,
+    current: Option<usize>,
+}
+
+impl<T: Item> LinkedInsertionSort<T> {
+    fn chains(&self) -> Vec<Vec<usize>> {
+        let inserting = self.insertion.map(|insertion| vec![insertion.node]).unwrap_or_default();
+
+        [self.nodes.chain(self.sorted, None), inserting, self.nodes.chain(self.unsorted, None)]
+            .into_iter()
+            .filter(|chain| !chain.is_empty())
+            .collect()
+    }
+
+    /// Do a step, giving the nodes it read and the nodes whose links it
+    /// wrote.
+    fn relink(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let Some(insertion) = &mut self.insertion else {
+            // Unlink the first of the unsorted nodes
+            let Some(node) = self.unsorted else {
+                return (vec![], vec![]);
+            };
+            self.unsorted = self.nodes.next[node];
+            self.nodes.next[node] = None;
+            self.insertion = Some(Insertion { node, previous: None, current: self.sorted });
+            return (vec![node], vec![node]);
+        };
+
+        // Equal nodes are walked past so that the sort is stable
+        let node = insertion.node;
+        if let Some(current) = insertion.current {
+            self.accesses.comparisons += 1;
+            if self.nodes.elements[current] <= self.nodes.elements[node] {
+                insertion.previous = Some(current);
+                insertion.current = self.nodes.next[current];
+                return (vec![node, current], vec![]);
+            }
+        }
+
+        let Insertion { previous, current, .. } = *insertion;
+        self.nodes.next[node] = current;
+        match previous {
+            Some(previous) => self.nodes.next[previous] = Some(node),
+            None => self.sorted = Some(node),
+        }
+        self.insertion = None;
+
+        let compared = current.map(|current| vec![node, current]).unwrap_or_default();
+        (compared, previous.into_iter().chain([node]).collect())
+    }
+}
+
+impl<T: Item> Default for LinkedInsertionSort<T> {
+    fn default() -> Self {
+        let mut linked_insertion_sort = Self {
+            name: "Linked Insertion Sort",
+            default_delay: time::Duration::from_millis(80),
+            nodes: Nodes::linked(vec![]),
+            sorted: None,
+            unsorted: None,
+            insertion: None,
+            accesses: Accesses::default(),
+        };
+        linked_insertion_sort.set_list(vec![Element::numbered(16)]);
+
+        linked_insertion_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for LinkedInsertionSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> HighlightedList<T> {
+        let highlighted = self.insertion
+            .map(|insertion| [Some(insertion.node), insertion.previous, insertion.current].into_iter().flatten().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        self.nodes.get_list(&self.chains(), &highlighted)
+    }
+
+    fn get_layout(&self) -> Layout {
+        Layout::Links(self.chains().concat())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.insertion.is_none() && self.unsorted.is_none()
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let (line, variables) = match self.insertion {
+            Some(insertion) => {
+                let advancing = insertion.current.is_some_and(|current| self.nodes.elements[current] <= self.nodes.elements[insertion.node]);
+                let variables = vec![
+                    ("node", self.nodes.describe(Some(insertion.node))),
+                    ("previous", self.nodes.describe(insertion.previous)),
+                    ("current", self.nodes.describe(insertion.current)),
+                ];
+                (if advancing { 5 } else { 6 }, variables)
+            },
+            None if self.unsorted.is_some() => (2, vec![("unsorted", self.nodes.describe(self.unsorted))]),
+            None => return Some(Pseudocode { lines: INSERTION_PSEUDOCODE, line: None, variables: vec![] }),
+        };
+
+        Some(Pseudocode { lines: INSERTION_PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.nodes = Nodes::linked(list);
+        self.sorted = None;
+        self.unsorted = if self.nodes.elements.is_empty() { None } else { Some(0) };
+        self.insertion = None;
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let before = self.chains();
+        let (read, written) = self.relink();
+        self.accesses.reads = Nodes::<T>::positions(&before, &read);
+        self.accesses.writes = Nodes::<T>::positions(&self.chains(), &written);
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
+
+/// Natural merge sort on a linked list. The list is cut into the runs that
+/// are already sorted, then neighbouring runs are merged by relinking their
+/// nodes until only one is left.
+#[derive(Clone)]
+pub struct LinkedMergeSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    nodes: Nodes<T>,
+    // The first node of each run, in order
+    runs: Vec<usize>,
+    // The run being merged with the one after it
+    index: usize,
+    merge: Option<Merge>,
+    accesses: Accesses,
+}
+
+/// The rest of the two runs being merged, and the first and last nodes of
+/// the merged run so far.
+#[derive(Clone, Copy, Debug)]
+struct Merge {
+    left: Option<usize>,
+    right: Option<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl<T: Item> LinkedMergeSort<T> {
+    fn chains(&self) -> Vec<Vec<usize>> {
+        let run = |head: &usize| self.nodes.chain(Some(*head), None);
+
+        let mut chains = self.runs[..self.index].iter().map(run).collect::<Vec<_>>();
+        match self.merge {
+            Some(merge) => {
+                chains.push(self.nodes.chain(merge.head, merge.tail));
+                chains.push(self.nodes.chain(merge.left, None));
+                chains.push(self.nodes.chain(merge.right, None));
+                chains.extend(self.runs[self.index + 2..].iter().map(run));
+            },
+            None => chains.extend(self.runs[self.index..].iter().map(run)),
+        }
+        chains.retain(|chain| !chain.is_empty());
+
+        chains
+    }
+
+    /// Do a step, giving the nodes it read and the nodes whose links it
+    /// wrote.
+    fn relink(&mut self) -> (Vec<usize>, Vec<usize>) {
+        if self.runs.len() <= 1 {
+            return (vec![], vec![]);
+        }
+
+        let Some(merge) = &mut self.merge else {
+            // Once every pair has been merged, go back to the start
+            if self.index + 1 >= self.runs.len() {
+                self.index = 0;
+            }
+            self.merge = Some(Merge {
+                left: Some(self.runs[self.index]),
+                right: Some(self.runs[self.index + 1]),
+                head: None,
+                tail: None,
+            });
+            return (vec![], vec![]);
+        };
+
+        // Ties are taken from the left so that the sort is stable, and once a
+        // run is used up the rest of the other is linked on all at once
+        let mut read = vec![];
+        let (node, finished) = match (merge.left, merge.right) {
+            (Some(left), Some(right)) => {
+                self.accesses.comparisons += 1;
+                read.extend([left, right]);
+                if self.nodes.elements[right] < self.nodes.elements[left] {
+                    merge.right = self.nodes.next[right];
+                    (right, false)
+                } else {
+                    merge.left = self.nodes.next[left];
+                    (left, false)
+                }
+            },
+            (Some(rest), None) | (None, Some(rest)) => {
+                merge.left = None;
+                merge.right = None;
+                (rest, true)
+            },
+            (None, None) => unreachable!("runs are never empty"),
+        };
+
+        let written = merge.tail.into_iter().collect();
+        match merge.tail {
+            Some(tail) => self.nodes.next[tail] = Some(node),
+            None => merge.head = Some(node),
+        }
+        merge.tail = Some(node);
+
+        if finished {
+            self.runs[self.index] = merge.head.unwrap_or(node);
+            self.runs.remove(self.index + 1);
+            self.index += 1;
+            self.merge = None;
+        }
+
+        (read, written)
+    }
+}
+
+impl<T: Item> Default for LinkedMergeSort<T> {
+    fn default() -> Self {
+        let mut linked_merge_sort = Self {
+            name: "Linked Merge Sort",
+            default_delay: time::Duration::from_millis(80),
+            nodes: Nodes::linked(vec![]),
+            runs: vec![],
+            index: 0,
+            merge: None,
+            accesses: Accesses::default(),
+        };
+        linked_merge_sort.set_list(vec![Element::numbered(16)]);
+
+        linked_merge_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for LinkedMergeSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> HighlightedList<T> {
+        let highlighted = self.merge
+            .map(|merge| [merge.tail, merge.left, merge.right].into_iter().flatten().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        self.nodes.get_list(&self.chains(), &highlighted)
+    }
+
+    fn get_layout(&self) -> Layout {
+        Layout::Links(self.chains().concat())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.runs.len() <= 1
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        if self.runs.len() <= 1 {
+            return Some(Pseudocode { lines: MERGE_PSEUDOCODE, line: None, variables: vec![] });
+        }
+
+        let mut variables = vec![("runs", self.runs.len().to_string())];
+        if let Some(merge) = self.merge {
+            variables.push(("left", self.nodes.describe(merge.left)));
+            variables.push(("right", self.nodes.describe(merge.right)));
+        }
+        let line = match self.merge {
+            Some(Merge { left: Some(_), right: Some(_), .. }) => 5,
+            Some(_) => 6,
+            None => 3,
+        };
+
+        Some(Pseudocode { lines: MERGE_PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.nodes = Nodes::linked(list);
+        self.index = 0;
+        self.merge = None;
+
+        // Cut the list wherever it goes down, leaving the runs that are
+        // already sorted
+        self.runs = if self.nodes.elements.is_empty() { vec![] } else { vec![0] };
+        for node in 1..self.nodes.elements.len() {
+            if self.nodes.elements[node] < self.nodes.elements[node - 1] {
+                self.nodes.next[node - 1] = None;
+                self.runs.push(node);
+            }
+        }
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let before = self.chains();
+        let (read, written) = self.relink();
+        self.accesses.reads = Nodes::<T>::positions(&before, &read);
+        self.accesses.writes = Nodes::<T>::positions(&self.chains(), &written);
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
@@ -1,24 +1,26 @@
-use super::SortingAlgorithm;
+use super::{Accesses, Element, SortingAlgorithm};
 use std::{ops::Range, time};
+use crate::items::Item;
 
-pub struct MergeInPlace {
+pub struct MergeInPlace<T> {
     // Statics
     name: String,
     delay: time::Duration,
 
     // Mutables
-    list: Vec<usize>,
+    list: Vec<Element<T>>,
     slices: RecursiveSlices,
 }
 
+#[allow(dead_code)] // Not read until stepping is implemented
 enum RecursiveSlices {
     Range((Range<usize>, bool)),
-    Split(Box<RecursiveSlices>, Box<RecursiveSlices>),
+    Split(Box<Self>, Box<Self>),
 }
 
-impl Default for MergeInPlace {
+impl<T: Item> Default for MergeInPlace<T> {
     fn default() -> Self {
-        let list = (1..=16).collect();
+        let list = Element::numbered(16);
         Self {
             name: "Merge Sort In-Place".to_string(),
             delay: time::Duration::from_millis(120),
@@ -28,16 +30,21 @@ impl Default for MergeInPlace {
     }
 }
 
-impl SortingAlgorithm for MergeInPlace {
+impl<T: Item> SortingAlgorithm<T> for MergeInPlace<T> {
     fn get_name(&self) -> &str {
         &self.name
     }
 
-    fn get_list(&self) -> (Vec<Vec<usize>>, Vec<(usize, usize)>) {
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
         (vec![self.list.clone()], vec![])
     }
 
-    fn set_list(&mut self, list: Vec<Vec<usize>>) {
+    // There's nothing to step through until stepping is implemented
+    fn is_finished(&self) -> bool {
+        true
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
         self.list = list.into_iter().flatten().collect();
         self.slices = RecursiveSlices::Range((0..self.list.len(), false));
     }
@@ -49,4 +56,9 @@ impl SortingAlgorithm for MergeInPlace {
     fn step(&mut self) {
         
     }
+
+    // Nothing is accessed until stepping is implemented
+    fn get_accesses(&self) -> Accesses {
+        Accesses::default()
+    }
 }
@@ -1,28 +1,103 @@
-use super::SortingAlgorithm;
+use super::{Accesses, CallFrame, CallPhase, Element, Highlight, Pseudocode, SortingAlgorithm, Tree, TreeNode};
 
 use std::time;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "merge_sort(list):",
+    "  if len(list) <= 1: return list",
+    "  left, right = split list in half",
+    "  left = merge_sort(left)",
+    "  right = merge_sort(right)",
+    "  return merge(left, right)",
+];
 
 #[derive(Clone)]
-pub struct MergeSort {
+pub struct MergeSort<T> {
     // Statics, defaulted by the sorting algorithm and kept that way
     name: &'static str,
     default_delay: time::Duration,
 
     // Mutables, these change as the sorting algorithm works.
-    merge_tree: MergeTree,
+    merge_tree: MergeTree<T>,
+    accesses: Accesses,
 }
 
 #[derive(Clone, Debug)]
-enum MergeTree {
-    Branch(Box<MergeTree>, Box<MergeTree>),
-    Leaf(Vec<usize>, bool), // The contents and whether they are sorted.
+enum MergeTree<T> {
+    Branch(Box<Self>, Box<Self>),
+    Leaf(Vec<Element<T>>, bool), // The contents and whether they are sorted.
 }
 
-impl MergeTree {
-    fn new(list: Vec<usize>) -> Self {
+impl<T: Item> MergeTree<T> {
+    const fn new(list: Vec<Element<T>>) -> Self {
         Self::Leaf(list, false)
     }
 
+    fn len(&self) -> usize {
+        match self {
+            Self::Leaf(list, _) => list.len(),
+            Self::Branch(left, right) => left.len() + right.len(),
+        }
+    }
+
+    /// The calls down to the part of the tree that the next step works on,
+    /// the way a recursive merge sort would have made them. The tree starts at
+    /// the index.
+    fn call_stack(&self, start: usize, frames: &mut Vec<CallFrame>) {
+        let range = start..start + self.len();
+        match self {
+            Self::Leaf(_, true) => {},
+            Self::Leaf(list, false) => frames.push(CallFrame {
+                range,
+                phase: if list.len() <= 1 { CallPhase::BaseCase } else { CallPhase::Splitting },
+            }),
+            Self::Branch(left, right) => match (&**left, &**right) {
+                (Self::Leaf(_, true), Self::Leaf(_, true)) => frames.push(CallFrame { range, phase: CallPhase::Merging }),
+                (Self::Leaf(_, true), _) => {
+                    frames.push(CallFrame { range, phase: CallPhase::SortingRightHalf });
+                    right.call_stack(start + left.len(), frames);
+                },
+                _ => {
+                    frames.push(CallFrame { range, phase: CallPhase::SortingLeftHalf });
+                    left.call_stack(start, frames);
+                },
+            },
+        }
+    }
+
+    /// Add the tree's nodes to the diagram, returning the index of its root.
+    /// Sorted leaves are highlighted, and so is the pair of leaves about to
+    /// be merged if the tree is on the way down to the next step.
+    fn add_nodes(&self, tree: &mut Tree<T>, next: bool) -> usize {
+        let index = tree.nodes.len();
+        tree.nodes.push(TreeNode { values: vec![], left: None, right: None });
+
+        match self {
+            Self::Leaf(list, sorted) => {
+                tree.nodes[index].values = list.iter().map(|element| element.value.clone()).collect();
+                if *sorted {
+                    tree.highlights.push((index, Highlight::Final));
+                }
+            },
+            Self::Branch(left, right) => {
+                // The next step goes down the same way it does in `step`
+                let merging = matches!((&**left, &**right), (Self::Leaf(_, true), Self::Leaf(_, true)));
+                let left_done = matches!(**left, Self::Leaf(_, true));
+
+                let left_index = left.add_nodes(tree, next && !merging && !left_done);
+                let right_index = right.add_nodes(tree, next && !merging && left_done);
+                if next && merging {
+                    tree.highlights.extend([(left_index, Highlight::Compare), (right_index, Highlight::Compare)]);
+                }
+                tree.nodes[index].left = Some(left_index);
+                tree.nodes[index].right = Some(right_index);
+            },
+        }
+
+        index
+    }
+
     fn flatten(&self) -> Vec<Self> {
         match self {
             Self::Leaf(..) => vec![self.clone()],
@@ -33,27 +108,28 @@ impl MergeTree {
     }
 }
 
-impl Default for MergeSort {
+impl<T: Item> Default for MergeSort<T> {
     fn default() -> Self {
         Self {
             name: "Merge Sort",
             default_delay: time::Duration::from_millis(120),
-            merge_tree: MergeTree::new((1..=16).collect()),
+            merge_tree: MergeTree::new(Element::numbered(16)),
+            accesses: Accesses::default(),
         }
     }
 }
 
-impl SortingAlgorithm for MergeSort {
+impl<T: Item> SortingAlgorithm<T> for MergeSort<T> {
     fn get_name(&self) -> &str {
         self.name
     }
 
-    fn get_list(&self) -> (Vec<Vec<usize>>, Vec<(usize, usize)>) {
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
         let flattened_tree = self.merge_tree.flatten();
         let (
             lists,
             sortednesses,
-        ): (Vec<Vec<usize>>, Vec<bool>) = flattened_tree
+        ): (Vec<Vec<Element<T>>>, Vec<bool>) = flattened_tree
             .into_iter()
             .map(|tree| {
                 let MergeTree::Leaf(list, sorted) = tree else { panic!("Somehow this is a branch") };
@@ -78,7 +154,50 @@ impl SortingAlgorithm for MergeSort {
         (lists, indexes)
     }
 
-    fn set_list(&mut self, list: Vec<Vec<usize>>) {
+    fn get_call_stack(&self) -> Vec<CallFrame> {
+        let mut frames = vec![];
+        self.merge_tree.call_stack(0, &mut frames);
+
+        frames
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.merge_tree, MergeTree::Leaf(_, true))
+    }
+
+    // The innermost call is the one the next step works on
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let frames = self.get_call_stack();
+        let Some(frame) = frames.last() else {
+            return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] });
+        };
+
+        let line = match frame.phase {
+            CallPhase::BaseCase => 1,
+            CallPhase::Splitting => 2,
+            CallPhase::SortingLeftHalf => 3,
+            CallPhase::SortingRightHalf => 4,
+            // Merging is the only other phase of merge sort's calls
+            _ => 5,
+        };
+        let variables = vec![
+            ("depth", (frames.len() - 1).to_string()),
+            ("lo", frame.range.start.to_string()),
+            ("hi", frame.range.end.to_string()),
+            ("len(list)", frame.range.len().to_string()),
+        ];
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn get_tree(&self) -> Option<Tree<T>> {
+        let mut tree = Tree { nodes: vec![], root: Some(0), highlights: vec![] };
+        self.merge_tree.add_nodes(&mut tree, true);
+
+        Some(tree)
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
         self.merge_tree = MergeTree::new(list.into_iter().flatten().collect());
     }
 
@@ -86,14 +205,18 @@ impl SortingAlgorithm for MergeSort {
         self.default_delay
     }
 
+    // Splitting a list only makes the calls, so merging is the only step that
+    // touches the elements
     fn step(&mut self) {
-        fn recurse_down(tree: &mut MergeTree) {
+        // Each leaf is shown as a list, starting from the given one. The right
+        // side is only gone down once the left is a single sorted leaf.
+        fn recurse_down<T: Item>(tree: &mut MergeTree<T>, first_leaf: usize, accesses: &mut Accesses) {
             match tree {
                 MergeTree::Leaf(ref mut list, ref mut sorted) => {
                     if *sorted {
                         return;
                     }
-                    if list.len() == 1 {
+                    if list.len() <= 1 {
                         *sorted = true;
                         return;
                     }
@@ -111,24 +234,24 @@ impl SortingAlgorithm for MergeSort {
                     if let MergeTree::Leaf(ref l_list, l_sorted) = **left {
                         if let MergeTree::Leaf(ref r_list, r_sorted) = **right {
                             if l_sorted && r_sorted {
-                                option_leaf = Some(MergeTree::Leaf(merge(l_list, r_list), true));
+                                option_leaf = Some(MergeTree::Leaf(merge(l_list, r_list, first_leaf, accesses), true));
                             }
                         }
                     }
 
-                    if option_leaf.is_some() {
-                        *tree = option_leaf.unwrap();
+                    if let Some(leaf) = option_leaf {
+                        *tree = leaf;
                     } else {
                         match **left {
                             MergeTree::Leaf(_, sorted) => {
                                 if sorted {
-                                    recurse_down(right);
+                                    recurse_down(right, first_leaf + 1, accesses);
                                 } else {
-                                    recurse_down(left);
+                                    recurse_down(left, first_leaf, accesses);
                                 }
                             },
                             MergeTree::Branch(..) => {
-                                recurse_down(left);
+                                recurse_down(left, first_leaf, accesses);
                             },
                         }
                     }
@@ -136,11 +259,18 @@ impl SortingAlgorithm for MergeSort {
             }
         }
 
-        recurse_down(&mut self.merge_tree);
+        self.accesses = Accesses::default();
+        recurse_down(&mut self.merge_tree, 0, &mut self.accesses);
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
     }
 }
 
-fn merge(a: &[usize], b: &[usize]) -> Vec<usize> {
+/// Merge the lists, which are shown as the given list and the one after it.
+/// The merged list takes the place of the first.
+fn merge<T: Item>(a: &[Element<T>], b: &[Element<T>], a_list: usize, accesses: &mut Accesses) -> Vec<Element<T>> {
     let mut new_list = vec![];
 
     let mut a_index = 0;
@@ -148,21 +278,52 @@ fn merge(a: &[usize], b: &[usize]) -> Vec<usize> {
 
     loop {
         if a_index == a.len() {
+            accesses.reads.extend((b_index..b.len()).map(|index| (a_list + 1, index)));
+            accesses.writes.extend((new_list.len()..a.len() + b.len()).map(|index| (a_list, index)));
             new_list.extend_from_slice(&b[b_index..]);
             break;
         } else if b_index == b.len() {
+            accesses.reads.extend((a_index..a.len()).map(|index| (a_list, index)));
+            accesses.writes.extend((new_list.len()..a.len() + b.len()).map(|index| (a_list, index)));
             new_list.extend_from_slice(&a[a_index..]);
             break;
         }
 
-        if a[a_index] < b[b_index] {
-            new_list.push(a[a_index]);
-            a_index += 1;
-        } else {
-            new_list.push(b[b_index]);
+        // Ties are taken from the left so that the sort is stable
+        accesses.compare((a_list, a_index), (a_list + 1, b_index));
+        accesses.write((a_list, new_list.len()));
+        if b[b_index] < a[a_index] {
+            new_list.push(b[b_index].clone());
             b_index += 1;
+        } else {
+            new_list.push(a[a_index].clone());
+            a_index += 1;
         }
     }
 
     new_list
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_count_their_comparisons_and_writes() {
+        // Every level of merges writes the whole list, and splitting costs
+        // nothing
+        let mut merge_sort = MergeSort::<usize>::default();
+        merge_sort.set_list(vec![Element::tagged([5, 2, 4, 6, 1, 3, 2, 6])]);
+        let (mut comparisons, mut writes) = (0, 0);
+        while !merge_sort.is_finished() {
+            merge_sort.step();
+            let accesses = merge_sort.get_accesses();
+            comparisons += accesses.comparisons;
+            writes += accesses.write_count();
+        }
+
+        assert_eq!((comparisons, writes), (17, 24));
+        let sorted = merge_sort.get_list().0.concat().into_iter().map(|element| element.value).collect::<Vec<_>>();
+        assert_eq!(sorted, [1, 2, 2, 3, 4, 5, 6, 6]);
+    }
+}
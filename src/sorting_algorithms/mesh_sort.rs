@@ -0,0 +1,443 @@
+use super::{Accesses, Element, HighlightedList, Layout, Pseudocode, SortingAlgorithm};
+
+use std::{collections::VecDeque, time};
+use eframe::egui;
+use crate::items::Item;
+
+/// The ways of sorting a grid that a mesh sort follows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Plan {
+    /// Shear over and over until the grid is in snake order.
+    Shear,
+    /// Marberg and Gafni's rotatesort, which rotates rows and columns between
+    /// the sorts so that a few shears are always enough.
+    Rotate,
+}
+
+/// Something done to every row or every column of the grid at once.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Operation {
+    /// Sort every row to the right, or every other one to the left when
+    /// sorting in snake order.
+    SortRows { snake: bool },
+    /// Sort every column downwards.
+    SortColumns,
+    /// Cut the rows into slices of the width, and rotate each row of a slice
+    /// to the right by its row number times the step.
+    RotateRows { slice: usize, step: usize },
+    /// Cut the columns into slices of the height, and rotate each column of a
+    /// slice down by its column number times the step.
+    RotateColumns { slice: usize, step: usize },
+}
+
+const SHEARSORT_PSEUDOCODE: &[&str] = &[
+    "shearsort(grid):",
+    "  while grid isn't in snake order:",
+    "    sort every row, odd rows backwards",
+    "    sort every column",
+    "  sort every row",
+];
+
+const ROTATESORT_PSEUDOCODE: &[&str] = &[
+    "rotatesort(grid):",
+    "  balance: sort columns, rotate rows in slices, sort columns",
+    "  unblock: rotate rows, sort columns",
+    "  balance sideways: sort rows, rotate columns in slices, sort rows",
+    "  unblock: rotate rows, sort columns",
+    "  shear three times:",
+    "    sort every row, odd rows backwards",
+    "    sort every column",
+    "  while grid isn't in snake order:",
+    "    sort every row, odd rows backwards",
+    "    sort every column",
+    "  sort every row",
+];
+
+/// Sorting algorithms for a mesh of processors, where the list is laid out in
+/// a grid and each processor can only swap with its neighbours. Rows and
+/// columns are sorted with odd-even transposition sort, every one of them at
+/// once, and the grid ends up sorted row by row. Cells past the end of the
+/// list are empty, and sort after everything.
+#[derive(Clone)]
+pub struct MeshSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+    plan: Plan,
+    // How many columns the grid has, or 0 to make it as square as it can be
+    columns: usize,
+
+    // Mutables, these change as the sorting algorithm works.
+    grid: Vec<Vec<Option<Element<T>>>>,
+    // The operations still to do, each with the line of pseudocode it's from
+    operations: VecDeque<(Operation, usize)>,
+    // How many rounds of the sort being done have gone by, and how many of
+    // the last ones didn't swap anything
+    round: usize,
+    quiet_rounds: usize,
+    // The cells that were read and the ones that moved in the last step
+    read: Vec<(usize, usize)>,
+    moved: Vec<(usize, usize)>,
+    accesses: Accesses,
+}
+
+impl<T: Item> MeshSort<T> {
+    pub fn shearsort() -> Self {
+        Self::new("Shearsort", Plan::Shear)
+    }
+
+    pub fn rotate_sort() -> Self {
+        Self::new("Rotatesort", Plan::Rotate)
+    }
+
+    fn new(name: &'static str, plan: Plan) -> Self {
+        let mut mesh_sort = Self {
+            name,
+            default_delay: time::Duration::from_millis(150),
+            plan,
+            columns: 0,
+            grid: vec![],
+            operations: VecDeque::new(),
+            round: 0,
+            quiet_rounds: 0,
+            read: vec![],
+            moved: vec![],
+            accesses: Accesses::default(),
+        };
+        mesh_sort.set_list(vec![Element::numbered(16)]);
+
+        mesh_sort
+    }
+
+    fn width(&self) -> usize {
+        self.grid.first().map_or(0, Vec::len)
+    }
+
+    /// Whether the first cell belongs after the second.
+    fn is_out_of_order(first: Option<&Element<T>>, second: Option<&Element<T>>) -> bool {
+        match (first, second) {
+            (Some(first), Some(second)) => first > second,
+            (None, Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// The cells in the order the grid reads, row by row, going back and
+    /// forth in snake order or left to right otherwise.
+    fn reading_order(&self, snake: bool) -> Vec<(usize, usize)> {
+        let columns = self.width();
+
+        (0..self.grid.len())
+            .flat_map(|row| {
+                let backwards = snake && row % 2 == 1;
+                (0..columns).map(move |column| (row, if backwards { columns - 1 - column } else { column }))
+            })
+            .collect()
+    }
+
+    /// Whether the grid reads in order, in snake order or not.
+    fn is_in_order(&self, snake: bool) -> bool {
+        !self.reading_order(snake).windows(2).any(|pair| {
+            Self::is_out_of_order(self.grid[pair[0].0][pair[0].1].as_ref(), self.grid[pair[1].0][pair[1].1].as_ref())
+        })
+    }
+
+    /// Whether the grid reads in order, counting the comparisons made to find
+    /// out.
+    fn check_order(&mut self, snake: bool) -> bool {
+        for pair in self.reading_order(snake).windows(2) {
+            let (first, second) = (self.grid[pair[0].0][pair[0].1].as_ref(), self.grid[pair[1].0][pair[1].1].as_ref());
+            if first.is_some() && second.is_some() {
+                self.read.extend([pair[0], pair[1]]);
+                self.accesses.comparisons += 1;
+            }
+            if Self::is_out_of_order(first, second) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Which of the cells have an element in them.
+    fn filled(&self) -> Vec<Vec<bool>> {
+        self.grid.iter().map(|row| row.iter().map(Option::is_some).collect()).collect()
+    }
+
+    /// Where the cells' elements are in the list, given which cells are
+    /// filled, leaving out the empty cells. The empty cells aren't in the
+    /// list, so the ones before each cell are skipped over.
+    fn positions(filled: &[Vec<bool>], cells: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        cells
+            .iter()
+            .filter(|(row, column)| filled[*row][*column])
+            .map(|(row, column)| (*row, filled[*row][..*column].iter().filter(|filled| **filled).count()))
+            .collect()
+    }
+
+    const fn pseudocode(&self) -> &'static [&'static str] {
+        match self.plan {
+            Plan::Shear => SHEARSORT_PSEUDOCODE,
+            Plan::Rotate => ROTATESORT_PSEUDOCODE,
+        }
+    }
+
+    /// The operations to shear the grid once, or to sort its rows once it's
+    /// in snake order, with their lines of pseudocode.
+    fn shear(&self, in_snake_order: bool) -> Vec<(Operation, usize)> {
+        let lines = self.pseudocode().len();
+        if in_snake_order {
+            vec![(Operation::SortRows { snake: false }, lines - 1)]
+        } else {
+            vec![(Operation::SortRows { snake: true }, lines - 3), (Operation::SortColumns, lines - 2)]
+        }
+    }
+
+    /// The operations that rotatesort starts with, before it shears. Slices
+    /// are about as many cells across as there are slices.
+    fn rotations(&self) -> Vec<(Operation, usize)> {
+        let (rows, columns) = (self.grid.len(), self.width());
+        let (slice_width, slice_height) = (ceil_sqrt(columns), ceil_sqrt(rows));
+        let unblock = |line| [
+            (Operation::RotateRows { slice: columns, step: slice_width }, line),
+            (Operation::SortColumns, line),
+        ];
+        let shear = [(Operation::SortRows { snake: true }, 6), (Operation::SortColumns, 7)];
+
+        // Balance the vertical slices, unblock, balance the horizontal slices
+        // as if they were on their side, unblock, then shear three times
+        let mut operations = vec![
+            (Operation::SortColumns, 1),
+            (Operation::RotateRows { slice: slice_width, step: 1 }, 1),
+            (Operation::SortColumns, 1),
+        ];
+        operations.extend(unblock(2));
+        operations.extend([
+            (Operation::SortRows { snake: false }, 3),
+            (Operation::RotateColumns { slice: slice_height, step: 1 }, 3),
+            (Operation::SortRows { snake: false }, 3),
+        ]);
+        operations.extend(unblock(4));
+        operations.extend(shear.repeat(3));
+
+        operations
+    }
+
+    /// One round of odd-even transposition sort on every line of cells,
+    /// given as a list of cell positions and whether to sort it backwards.
+    fn transpose_round(&mut self, lines: &[(Vec<(usize, usize)>, bool)]) -> bool {
+        let mut swapped = false;
+        for (line, backwards) in lines {
+            for pair in line[(self.round % 2).min(line.len())..].chunks_exact(2) {
+                let ((first_row, first_column), (second_row, second_column)) = (pair[0], pair[1]);
+                let first = self.grid[first_row][first_column].as_ref();
+                let second = self.grid[second_row][second_column].as_ref();
+                if first.is_some() && second.is_some() {
+                    self.read.extend([pair[0], pair[1]]);
+                    self.accesses.comparisons += 1;
+                }
+                let out_of_order = if *backwards {
+                    Self::is_out_of_order(second, first)
+                } else {
+                    Self::is_out_of_order(first, second)
+                };
+
+                if out_of_order {
+                    let first = self.grid[first_row][first_column].take();
+                    self.grid[first_row][first_column] = self.grid[second_row][second_column].take();
+                    self.grid[second_row][second_column] = first;
+                    self.moved.extend([pair[0], pair[1]]);
+                    swapped = true;
+                }
+            }
+        }
+
+        swapped
+    }
+
+    fn rotate(&mut self, cells: &[(usize, usize)], amount: usize) {
+        let amount = amount % cells.len().max(1);
+        if amount == 0 {
+            return;
+        }
+
+        self.read.extend(cells);
+        let mut values = cells.iter().map(|(row, column)| self.grid[*row][*column].take()).collect::<Vec<_>>();
+        values.rotate_right(amount);
+        for (cell, value) in cells.iter().zip(values) {
+            self.grid[cell.0][cell.1] = value;
+        }
+        self.moved.extend(cells);
+    }
+}
+
+/// The square root of the number, rounded up.
+const fn ceil_sqrt(number: usize) -> usize {
+    let root = number.isqrt();
+
+    if root * root < number { root + 1 } else { root }
+}
+
+impl<T: Item> SortingAlgorithm<T> for MeshSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> HighlightedList<T> {
+        let list = self.grid
+            .iter()
+            .map(|row| row.iter().flatten().cloned().collect())
+            .collect();
+        let highlights = Self::positions(&self.filled(), &self.moved);
+
+        (list, highlights)
+    }
+
+    fn get_layout(&self) -> Layout {
+        let cells = self.grid
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, row)| {
+                row.iter().enumerate().filter(|(_, cell)| cell.is_some()).map(move |(column, _)| (row_index, column))
+            })
+            .collect();
+
+        Layout::Grid(cells)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.operations.is_empty() && self.is_in_order(false)
+    }
+
+    // The shears that follow the planned operations are only queued up once
+    // they're reached
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let line = match self.operations.front() {
+            Some((_, line)) => Some(*line),
+            None if self.is_in_order(false) => None,
+            None => self.shear(self.is_in_order(true)).first().map(|(_, line)| *line),
+        };
+        let variables = vec![
+            ("rows", self.grid.len().to_string()),
+            ("columns", self.width().to_string()),
+            ("round", self.round.to_string()),
+        ];
+
+        Some(Pseudocode { lines: self.pseudocode(), line, variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        let mut elements = list.into_iter().flatten().map(Some).collect::<Vec<_>>();
+        let length = elements.len();
+        let columns = match self.columns {
+            0 => ceil_sqrt(length),
+            columns => columns.min(length),
+        }.max(1);
+        elements.resize(length.div_ceil(columns) * columns, None);
+
+        self.grid = elements.chunks(columns).map(<[_]>::to_vec).collect();
+        self.operations = match self.plan {
+            Plan::Shear => VecDeque::new(),
+            Plan::Rotate => self.rotations().into(),
+        };
+        self.round = 0;
+        self.quiet_rounds = 0;
+        self.read = vec![];
+        self.moved = vec![];
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.read = vec![];
+        self.moved = vec![];
+        self.accesses = Accesses::default();
+        let filled = self.filled();
+
+        // Once the planned operations are done, shear until the grid is in
+        // snake order, then sort the rows to put it in order row by row
+        if self.operations.is_empty() && !self.check_order(false) {
+            let in_snake_order = self.check_order(true);
+            self.operations.extend(self.shear(in_snake_order));
+        }
+        let Some((operation, _)) = self.operations.front().copied() else {
+            self.accesses.reads = Self::positions(&filled, &self.read);
+            return;
+        };
+
+        let (rows, columns) = (self.grid.len(), self.width());
+        let rounds = match operation {
+            Operation::SortRows { snake } => {
+                let lines = (0..rows)
+                    .map(|row| ((0..columns).map(|column| (row, column)).collect(), snake && row % 2 == 1))
+                    .collect::<Vec<_>>();
+                Some((lines, columns))
+            },
+            Operation::SortColumns => {
+                let lines = (0..columns)
+                    .map(|column| ((0..rows).map(|row| (row, column)).collect(), false))
+                    .collect::<Vec<_>>();
+                Some((lines, rows))
+            },
+            Operation::RotateRows { slice, step } => {
+                for row in 0..rows {
+                    for start in (0..columns).step_by(slice.max(1)) {
+                        let cells = (start..(start + slice).min(columns)).map(|column| (row, column)).collect::<Vec<_>>();
+                        self.rotate(&cells, row * step);
+                    }
+                }
+                None
+            },
+            Operation::RotateColumns { slice, step } => {
+                for column in 0..columns {
+                    for start in (0..rows).step_by(slice.max(1)) {
+                        let cells = (start..(start + slice).min(rows)).map(|row| (row, column)).collect::<Vec<_>>();
+                        self.rotate(&cells, column * step);
+                    }
+                }
+                None
+            },
+        };
+
+        // A sort is done after as many rounds as its lines are long, or once
+        // a round of each parity has gone by without a swap
+        let done = rounds.is_none_or(|(lines, length)| {
+            let swapped = self.transpose_round(&lines);
+            self.quiet_rounds = if swapped { 0 } else { self.quiet_rounds + 1 };
+            self.round += 1;
+            self.round >= length || self.quiet_rounds >= 2
+        });
+        if done {
+            self.operations.pop_front();
+            self.round = 0;
+            self.quiet_rounds = 0;
+        }
+        self.accesses.reads = Self::positions(&filled, &self.read);
+        self.accesses.writes = Self::positions(&self.filled(), &self.moved);
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+
+    fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        let mut columns = self.columns;
+
+        ui.horizontal(|ui| {
+            ui.label("Grid columns:");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::RIGHT), |ui| {
+                ui.add(egui::DragValue::new(&mut columns)
+                    .speed(0.05)
+                    .range(0..=64)
+                    .custom_formatter(|columns, _| if columns == 0.0 { "Square".to_string() } else { columns.to_string() }));
+            });
+        });
+
+        if columns != self.columns {
+            self.columns = columns;
+            self.set_list(self.get_list().0);
+        }
+    }
+}
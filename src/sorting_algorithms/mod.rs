@@ -1,31 +1,622 @@
+pub mod bead_sort;
 pub mod bogo_sort;
+pub mod bucket_sort;
+pub mod flash_sort;
+pub mod inner_sort;
+pub mod linked_lists;
 pub mod merge_sort;
 pub mod merge_in_place;
+pub mod mesh_sort;
+pub mod msd_radix_sort;
+pub mod multikey_quicksort;
+pub mod partial_sort;
+pub mod patience_sort;
+pub mod row_sort;
+pub mod selection;
+pub mod spaghetti_sort;
+pub mod tournament_sort;
+pub mod tree_sort;
 
-use std::time;
+use std::{cmp, ops::Range, time};
+use eframe::egui;
+use crate::{items::Item, order::Order};
 
-pub fn get_available_algorithms() -> Vec<Box<dyn SortingAlgorithm>> {
-    vec![
+pub fn get_available_algorithms<T: Item>() -> Vec<Box<dyn SortingAlgorithm<T>>> {
+    let mut algorithms: Vec<Box<dyn SortingAlgorithm<T>>> = vec![
         Box::new(bogo_sort::BogoSort::default()),
         Box::new(merge_sort::MergeSort::default()),
         Box::new(merge_in_place::MergeInPlace::default()),
-    ]
+        Box::new(bucket_sort::BucketSort::default()),
+        Box::new(flash_sort::FlashSort::default()),
+        Box::new(tree_sort::TreeSort::default()),
+        Box::new(tournament_sort::TournamentSort::default()),
+        Box::new(patience_sort::PatienceSort::default()),
+        Box::new(linked_lists::LinkedInsertionSort::default()),
+        Box::new(linked_lists::LinkedMergeSort::default()),
+        Box::new(mesh_sort::MeshSort::shearsort()),
+        Box::new(mesh_sort::MeshSort::rotate_sort()),
+    ];
+    algorithms.extend(T::get_extra_algorithms());
+    algorithms.extend([
+        Box::new(spaghetti_sort::SpaghettiSort::default()) as Box<dyn SortingAlgorithm<T>>,
+        Box::new(selection::Selection::quickselect()),
+        Box::new(selection::Selection::introselect()),
+        Box::new(selection::Selection::median_of_medians()),
+        Box::new(partial_sort::PartialSort::default()),
+    ]);
+
+    algorithms
+}
+
+/// An element of a list. Elements are compared by their value alone, going
+/// by the order they carry, and the tag says where the element started out
+/// so that equal elements can still be told apart.
+#[derive(Clone, Copy, Debug)]
+pub struct Element<T> {
+    pub value: T,
+    pub tag: usize,
+    pub order: Order,
+}
+
+impl<T: Item> Element<T> {
+    /// Make a list out of the values, tagged with their positions.
+    pub fn tagged(values: impl IntoIterator<Item = T>) -> Vec<Self> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(tag, value)| Self { value, tag, order: Order::default() })
+            .collect()
+    }
+
+    /// Make the list of the items for the numbers from 1 to the length, in
+    /// order.
+    pub fn numbered(length: usize) -> Vec<Self> {
+        Self::tagged((1..=length).map(|number| T::from_number(number, length)))
+    }
+
+    /// The element, to be sorted in the order.
+    pub const fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// How tall the element is going by its order, for the algorithms that
+    /// sort by height.
+    pub fn height(&self) -> f32 {
+        self.order.height(&self.value)
+    }
+
+    /// The indexes that hold a different element after a step than they did
+    /// before it, or the same one with a different value, going through the
+    /// lists in order. These are the ones the step wrote to.
+    pub fn changed_indexes(before: &[Vec<Self>], after: &[Vec<Self>]) -> Vec<usize> {
+        // The values are compared as they are rather than by the order, which
+        // can have different values be equal
+        before
+            .iter()
+            .flatten()
+            .zip(after.iter().flatten())
+            .enumerate()
+            .filter(|(_, (before, after))| before.tag != after.tag || before.value != after.value)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Whether the elements of each value are still in the order they
+    /// started out in, which they always are after a stable sort.
+    pub fn is_in_original_order(list: &[Self]) -> bool {
+        // A stable sort of the list puts equal elements in the order they're
+        // in now
+        let mut sorted = list.iter().collect::<Vec<_>>();
+        sorted.sort();
+
+        sorted.windows(2).all(|pair| pair[0] != pair[1] || pair[0].tag < pair[1].tag)
+    }
+}
+
+impl<T: Item> PartialEq for Element<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<T: Item> Eq for Element<T> {}
+
+impl<T: Item> PartialOrd for Element<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Item> Ord for Element<T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.order.compare(&self.value, &other.value)
+    }
+}
+
+/// The height that bars (or the like) are measured up from. It's a gap
+/// below the lowest height, so that even the lowest one can be seen.
+pub fn height_floor(heights: impl IntoIterator<Item = f32>) -> f32 {
+    let heights = heights.into_iter().collect::<Vec<_>>();
+    let min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+    // The gap is the average step between the heights
+    if min < max {
+        min - (max - min) / (heights.len() - 1) as f32
+    } else {
+        min - 1.0
+    }
+}
+
+/// The byte of the string at the position, or `None` once the string has
+/// ended. Strings that have ended sort before any that go on.
+pub fn character_at(string: &str, position: usize) -> Option<u8> {
+    string.as_bytes().get(position).copied()
+}
+
+/// The character as pseudocode shows it, or `end` once the string has ended.
+pub fn describe_character(character: Option<u8>) -> String {
+    character.map_or_else(|| "end".to_string(), |byte| format!("'{}'", char::from(byte).escape_default()))
+}
+
+/// What a sorting algorithm is trying to do to its list.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Goal {
+    /// Sort the whole list.
+    Sorted,
+    /// Put the element that belongs at the index there, with nothing bigger
+    /// before it and nothing smaller after it.
+    Selected(usize),
+    /// Sort the list up to and including the index.
+    SortedUpTo(usize),
+    /// Shuffle the list. There's no telling a shuffled list apart from any
+    /// other, so this is reached once the algorithm has finished.
+    Shuffled,
+}
+
+impl Goal {
+    /// Whether the list has reached the goal, given the sorted version of it
+    /// and whether the algorithm has finished. A list that's sorted before
+    /// the algorithm has finished may still be moved around.
+    pub fn is_reached<T: Ord>(self, list: &[T], sorted: &[T], finished: bool) -> bool {
+        if list.len() != sorted.len() {
+            return false;
+        }
+
+        match self {
+            Self::Sorted => finished && list == sorted,
+            Self::Selected(index) => list.get(index).map_or(list.is_empty(), |selected| {
+                *selected == sorted[index]
+                    && list[..index].iter().all(|value| value <= selected)
+                    && list[index + 1..].iter().all(|value| value >= selected)
+            }),
+            Self::SortedUpTo(index) => list.is_empty() || (list.len() > index && list[..=index] == sorted[..=index]),
+            Self::Shuffled => finished,
+        }
+    }
+
+    /// Whether the index is one that the goal is about.
+    pub const fn is_target(self, index: usize) -> bool {
+        match self {
+            Self::Sorted | Self::Shuffled => false,
+            Self::Selected(target) => index == target,
+            Self::SortedUpTo(target) => index <= target,
+        }
+    }
+}
+
+/// What an index of the list is highlighted for. When an index is highlighted
+/// for more than one thing, the later one is shown.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Highlight {
+    /// The element is where it'll be once the list is sorted, and won't move
+    /// again.
+    Final,
+    /// The index is one end of the range being worked on.
+    Boundary,
+    /// The element is being compared.
+    Compare,
+    /// The element was just written there.
+    Write,
+    /// The element was just swapped with another.
+    Swap,
+    /// The element is the pivot that others are compared against.
+    Pivot,
+}
+
+impl Highlight {
+    /// The highlights in the order they're listed in the legend.
+    pub const ALL: [Self; 6] = [
+        Self::Compare,
+        Self::Swap,
+        Self::Write,
+        Self::Pivot,
+        Self::Boundary,
+        Self::Final,
+    ];
+
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Compare => "Compare",
+            Self::Swap => "Swap",
+            Self::Write => "Write",
+            Self::Pivot => "Pivot",
+            Self::Boundary => "Boundary",
+            Self::Final => "Sorted",
+        }
+    }
+
+    pub const fn color(self) -> egui::Color32 {
+        match self {
+            Self::Compare => egui::Color32::LIGHT_RED,
+            Self::Swap => egui::Color32::YELLOW,
+            Self::Write => egui::Color32::from_rgb(255, 160, 60),
+            Self::Pivot => egui::Color32::from_rgb(200, 120, 255),
+            Self::Boundary => egui::Color32::from_rgb(80, 200, 255),
+            Self::Final => egui::Color32::from_rgb(60, 170, 90),
+        }
+    }
+}
+
+/// How the list should be drawn.
+pub enum Layout {
+    /// One bar per element.
+    Bars,
+    /// A grid of beads threaded on vertical rods, one row per element. Each
+    /// row says which of the rods have a bead at that row.
+    Beads(Vec<Vec<bool>>),
+    /// One uncooked spaghetti rod per element, standing on a table. Holds how
+    /// far each rod is lifted off the table, and how high the hand coming down
+    /// onto the rods is, if there is one.
+    Rods(Vec<f32>, Option<f32>),
+    /// One node per element, each list being a chain of nodes linked by
+    /// arrows. Holds where each node is in memory, so that the nodes stay put
+    /// while they're relinked.
+    Links(Vec<usize>),
+    /// One cell per element in a grid, each list being a row of it. Holds the
+    /// row and column of each element's cell, since cells can be empty.
+    Grid(Vec<(usize, usize)>),
+}
+
+/// A binary tree that a sorting algorithm builds beside its list.
+pub struct Tree<T> {
+    pub nodes: Vec<TreeNode<T>>,
+    pub root: Option<usize>,
+    /// The indexes of the nodes that should be highlighted, and what for.
+    pub highlights: Vec<(usize, Highlight)>,
+}
+
+pub struct TreeNode<T> {
+    /// The values held by the node. Most trees hold one value per node at
+    /// most, but a node can hold a whole list.
+    pub values: Vec<T>,
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+}
+
+/// A call that a sorting algorithm has made on part of its list, for the
+/// algorithms that divide and conquer.
+pub struct CallFrame {
+    /// The part of the list the call is on, going through the lists in
+    /// order.
+    pub range: Range<usize>,
+    /// What the call is doing.
+    pub phase: CallPhase,
 }
 
-pub trait SortingAlgorithm {
+/// What a call is doing, either the work of its own or which of the calls it
+/// made it's waiting on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CallPhase {
+    /// The call's part is too short to need sorting.
+    BaseCase,
+    /// The call's part is being split in half.
+    Splitting,
+    /// The call's sorted halves are being merged.
+    Merging,
+    /// The call is waiting on its left half to be sorted.
+    SortingLeftHalf,
+    /// The call is waiting on its right half to be sorted.
+    SortingRightHalf,
+    /// The call is picking the pivot to partition by.
+    PickingPivot,
+    /// The call is sorting one of its groups of five to find its median.
+    SortingGroup,
+    /// The call is waiting on the median of its groups' medians.
+    SelectingMedian,
+    /// The call's part is being partitioned around the pivot.
+    Partitioning,
+    /// The call is waiting on the part smaller than its pivot to be sorted.
+    SortingSmallerPart,
+    /// The call is waiting on the part equal to its pivot to be sorted by the
+    /// next character.
+    SortingEqualPart,
+    /// The call is waiting on the part bigger than its pivot to be sorted.
+    SortingBiggerPart,
+    /// The call's part is being dealt into buckets.
+    Dealing,
+    /// The call's buckets are being put back into its part.
+    Gathering,
+    /// The call is waiting on one of its buckets to be sorted by the next
+    /// character.
+    SortingBucket,
+}
+
+impl CallPhase {
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::BaseCase => "Base case",
+            Self::Splitting => "Splitting",
+            Self::Merging => "Merging",
+            Self::SortingLeftHalf => "Sorting the left half",
+            Self::SortingRightHalf => "Sorting the right half",
+            Self::PickingPivot => "Picking a pivot",
+            Self::SortingGroup => "Sorting a group of five",
+            Self::SelectingMedian => "Selecting the median of medians",
+            Self::Partitioning => "Partitioning",
+            Self::SortingSmallerPart => "Sorting the smaller part",
+            Self::SortingEqualPart => "Sorting the equal part by the next character",
+            Self::SortingBiggerPart => "Sorting the bigger part",
+            Self::Dealing => "Dealing into buckets",
+            Self::Gathering => "Gathering the buckets",
+            Self::SortingBucket => "Sorting a bucket by the next character",
+        }
+    }
+}
+
+/// The steps of a sorting algorithm written out as pseudocode, with the line
+/// it's on and what its variables hold.
+pub struct Pseudocode {
+    pub lines: &'static [&'static str],
+    /// The index of the line that the next step runs, or `None` once the
+    /// algorithm has nothing left to run.
+    pub line: Option<usize>,
+    /// The names of the variables that matter at the line, and their values.
+    pub variables: Vec<(&'static str, String)>,
+}
+
+/// What one step of a sorting algorithm did with its elements. Positions are
+/// in the lists the algorithm shows, with reads going by the lists before the
+/// step and writes by the lists after it.
+#[derive(Clone, Default, Debug)]
+pub struct Accesses {
+    pub reads: Vec<(usize, usize)>,
+    pub writes: Vec<(usize, usize)>,
+    /// Writes to somewhere off the list, like a node of a tree beside it.
+    pub writes_aside: usize,
+    /// The comparisons made, counting each time an element's key is looked
+    /// at to place it (e.g. working out an element's bucket) as one.
+    pub comparisons: usize,
+}
+
+impl Accesses {
+    pub fn read(&mut self, position: (usize, usize)) {
+        self.reads.push(position);
+    }
+
+    pub fn write(&mut self, position: (usize, usize)) {
+        self.writes.push(position);
+    }
+
+    /// Swap the elements at the positions, reading and writing both. Swapping
+    /// a position with itself does nothing.
+    pub fn swap(&mut self, first: (usize, usize), second: (usize, usize)) {
+        if first != second {
+            self.reads.extend([first, second]);
+            self.writes.extend([first, second]);
+        }
+    }
+
+    /// Compare the elements at the positions.
+    pub fn compare(&mut self, first: (usize, usize), second: (usize, usize)) {
+        self.reads.extend([first, second]);
+        self.comparisons += 1;
+    }
+
+    /// Compare the element at the position with something off the list, or
+    /// look at its key.
+    pub fn compare_aside(&mut self, position: (usize, usize)) {
+        self.reads.push(position);
+        self.comparisons += 1;
+    }
+
+    /// The writes made, both to the lists and off them.
+    pub const fn write_count(&self) -> usize {
+        self.writes.len() + self.writes_aside
+    }
+}
+
+/// A list split up into sublists, and the positions in it that should be
+/// highlighted.
+pub type HighlightedList<T> = (Vec<Vec<Element<T>>>, Vec<(usize, usize)>);
+
+pub trait SortingAlgorithm<T: Item> {
     /// Get the name of the sorting algorithm.
     fn get_name(&self) -> &str;
 
     /// Get the list state of the sorting algorithm and a list of indexes that
     /// should be highlighted.
-    fn get_list(&self) -> (Vec<Vec<usize>>, Vec<(usize, usize)>);
+    fn get_list(&self) -> HighlightedList<T>;
+
+    /// Get the indexes of the list that are highlighted for something other
+    /// than being compared, like the pivot or the elements that are already
+    /// where they belong. Swaps and writes are found without the sorting
+    /// algorithm's help.
+    fn get_marks(&self) -> Vec<((usize, usize), Highlight)> {
+        vec![]
+    }
+
+    /// Get the group that indexes of the list belong to, e.g. the bucket an
+    /// element is headed for. Grouped bars are colored by their group.
+    fn get_groups(&self) -> Vec<((usize, usize), usize)> {
+        vec![]
+    }
+
+    /// Get what the sorting algorithm is trying to do to the list.
+    fn get_goal(&self) -> Goal {
+        Goal::Sorted
+    }
+
+    /// Whether the algorithm has nothing left to do, which is once its
+    /// pseudocode has returned. Lists can pass through being sorted before
+    /// then, so a sorted list isn't enough to tell.
+    fn is_finished(&self) -> bool;
+
+    /// Set the index that the sorting algorithm's goal is about, for the
+    /// algorithms whose goal isn't to sort the whole list.
+    fn set_target(&mut self, _target: usize) {}
+
+    /// Get how the list should be drawn.
+    fn get_layout(&self) -> Layout {
+        Layout::Bars
+    }
+
+    /// Get the tree the sorting algorithm is using, if it uses one.
+    fn get_tree(&self) -> Option<Tree<T>> {
+        None
+    }
+
+    /// Get the calls the sorting algorithm is in the middle of, outermost
+    /// first, for the algorithms that recurse.
+    fn get_call_stack(&self) -> Vec<CallFrame> {
+        vec![]
+    }
+
+    /// Get the pseudocode of the sorting algorithm, showing where it's up to.
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        None
+    }
+
+    /// Get the position of the character being compared in the highlighted
+    /// strings, for the algorithms that sort strings a character at a time.
+    fn get_character(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the sorting algorithm moves the elements of the list around, so
+    /// that it can be stable or not. Algorithms that only count the values
+    /// make new elements rather than moving the old ones.
+    fn moves_elements(&self) -> bool {
+        true
+    }
+
+    /// Whether the sorting algorithm can sort in the order. Algorithms that
+    /// can't carry the items along with their keys can only sort by value.
+    fn supports_order(&self, _order: Order) -> bool {
+        true
+    }
+
+    /// Whether the sorting algorithm can sort the list, or why it can't. Only
+    /// algorithms with limits on how big the values can be turn lists down.
+    fn check_list(&self, _list: &[Vec<Element<T>>]) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Seed the random numbers of the sorting algorithm, for the algorithms
+    /// that use them. The same seed and list always make the same run.
+    fn set_seed(&mut self, _seed: u64) {}
 
     /// Set the list state of the sorting algorithm.
-    fn set_list(&mut self, list: Vec<Vec<usize>>);
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>);
 
     /// Get the default delay of the sorting algorithm running
     fn get_delay(&self) -> time::Duration;
 
     /// Do one step of the sorting algorithm.
     fn step(&mut self);
+
+    /// Get what the last step read, wrote and compared, as the algorithm
+    /// counted it.
+    fn get_accesses(&self) -> Accesses;
+
+    /// Draw the settings specific to the sorting algorithm, if it has any.
+    fn draw_settings(&mut self, _ui: &mut egui::Ui) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use crate::items::{ByLength, Float, Pixel, Record};
+
+    /// Run every available algorithm to the end on a shuffled list with
+    /// repeated values, both ways around, and check that it reaches its goal.
+    fn run_every_algorithm<T: Item>() {
+        for descending in [false, true] {
+            let order = Order { descending, ..Order::default() };
+            for mut algorithm in get_available_algorithms::<T>().into_iter().filter(|algorithm| algorithm.supports_order(order)) {
+                let name = algorithm.get_name().to_string();
+                // Merge sort in place doesn't step yet, and bogo sort would
+                // take forever on a list of any length
+                let length = match name.as_str() {
+                    "Merge Sort In-Place" => continue,
+                    "Bogo Sort" => 5,
+                    _ => 40,
+                };
+
+                let mut values = (0..length).map(|index| index % 13 + 1).collect::<Vec<_>>();
+                values.shuffle(&mut StdRng::seed_from_u64(length as u64));
+                let list = Element::tagged(values.into_iter().map(|value| T::from_number(value, length)))
+                    .into_iter()
+                    .map(|element| element.with_order(order))
+                    .collect::<Vec<_>>();
+                let mut sorted = list.clone();
+                sorted.sort();
+
+                algorithm.set_target(length / 2);
+                algorithm.set_seed(0);
+                algorithm.set_list(vec![list]);
+                for _ in 0..100_000 {
+                    if algorithm.is_finished() {
+                        break;
+                    }
+                    algorithm.step();
+                }
+
+                let list = algorithm.get_list().0.concat();
+                assert!(algorithm.is_finished(), "{name} didn't finish on {:?}", T::KIND);
+                assert!(
+                    algorithm.get_goal().is_reached(&list, &sorted, true),
+                    "{name} didn't reach its goal on {:?}, descending: {descending}",
+                    T::KIND,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn algorithms_sort_unsigned_integers() {
+        run_every_algorithm::<usize>();
+    }
+
+    #[test]
+    fn algorithms_sort_signed_integers() {
+        run_every_algorithm::<i64>();
+    }
+
+    #[test]
+    fn algorithms_sort_floats() {
+        run_every_algorithm::<Float>();
+    }
+
+    #[test]
+    fn algorithms_sort_text() {
+        run_every_algorithm::<String>();
+    }
+
+    #[test]
+    fn algorithms_sort_text_by_length() {
+        run_every_algorithm::<ByLength>();
+    }
+
+    #[test]
+    fn algorithms_sort_records() {
+        run_every_algorithm::<Record>();
+    }
+
+    #[test]
+    fn algorithms_sort_pixels() {
+        run_every_algorithm::<Pixel>();
+    }
 }
@@ -0,0 +1,231 @@
+use super::{character_at, describe_character, Accesses, CallFrame, CallPhase, Element, Pseudocode, SortingAlgorithm};
+
+use std::{ops::Range, time};
+
+const PSEUDOCODE: &[&str] = &[
+    "msd_radix_sort(a, lo, hi, d):",
+    "  if hi - lo <= 1: return",
+    "  for each s in a[lo..hi]:",
+    "    append s to bucket[s[d]]",
+    "  put the buckets back into a[lo..hi]",
+    "  for each bucket of strings that go on:",
+    "    msd_radix_sort(a, its range, d + 1)",
+];
+
+/// Most significant digit first radix sort for strings. The range is dealt
+/// into buckets by the character at the current position, with the strings
+/// that have ended going first. Then each bucket is sorted the same way by
+/// the next character.
+#[derive(Clone)]
+pub struct MsdRadixSort {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<String>>,
+    // The ranges left to sort and the character position to sort them by,
+    // where the last one is sorted next
+    ranges: Vec<(Range<usize>, usize)>,
+    // The ranges that have been dealt out and are waiting on their buckets
+    // to be sorted, outermost first, with how many ranges were left to sort
+    // before their buckets were added
+    callers: Vec<(Range<usize>, usize)>,
+    // The buckets of the range being dealt out, and how many of its elements
+    // have been dealt
+    buckets: Vec<Vec<Element<String>>>,
+    dealt: usize,
+    accesses: Accesses,
+}
+
+impl MsdRadixSort {
+    /// The bucket for the string, with one for the strings that have ended
+    /// and one for each byte. Descending orders have the buckets the other
+    /// way around.
+    fn bucket_of(element: &Element<String>, position: usize) -> usize {
+        let bucket = character_at(&element.value, position).map_or(0, |byte| usize::from(byte) + 1);
+
+        if element.order.descending {
+            256 - bucket
+        } else {
+            bucket
+        }
+    }
+
+    /// Where the element at the index of the list is shown while nothing is
+    /// being dealt, which is split at the start of the range to sort next.
+    fn position(&self, index: usize) -> (usize, usize) {
+        match self.ranges.last() {
+            Some((range, _)) if index >= range.start => (1, index - range.start),
+            _ => (0, index),
+        }
+    }
+}
+
+impl Default for MsdRadixSort {
+    fn default() -> Self {
+        let mut msd_radix_sort = Self {
+            name: "MSD Radix Sort",
+            default_delay: time::Duration::from_millis(80),
+            list: vec![],
+            ranges: vec![],
+            callers: vec![],
+            buckets: vec![],
+            dealt: 0,
+            accesses: Accesses::default(),
+        };
+        msd_radix_sort.set_list(vec![Element::numbered(16)]);
+
+        msd_radix_sort
+    }
+}
+
+impl SortingAlgorithm<String> for MsdRadixSort {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<String>>>, Vec<(usize, usize)>) {
+        let Some((range, _)) = self.ranges.last() else {
+            return (vec![self.list.clone()], vec![]);
+        };
+
+        // The buckets are shown between the elements before the range and
+        // the ones still to be dealt
+        let mut lists = vec![self.list[..range.start].to_vec()];
+        lists.extend(self.buckets.iter().filter(|bucket| !bucket.is_empty()).cloned());
+        lists.push(self.list[range.start + self.dealt..].to_vec());
+        let highlights = if self.dealt < range.len() { vec![(lists.len() - 1, 0)] } else { vec![] };
+
+        (lists, highlights)
+    }
+
+    // A caller is done once every range added after it has been sorted
+    fn get_call_stack(&self) -> Vec<CallFrame> {
+        let Some((range, _)) = self.ranges.last() else {
+            return vec![];
+        };
+
+        let mut frames = self.callers
+            .iter()
+            .take_while(|(_, pending)| *pending < self.ranges.len())
+            .map(|(caller, _)| CallFrame { range: caller.clone(), phase: CallPhase::SortingBucket })
+            .collect::<Vec<_>>();
+        frames.push(CallFrame {
+            range: range.clone(),
+            phase: if range.len() <= 1 {
+                CallPhase::BaseCase
+            } else if self.dealt < range.len() {
+                CallPhase::Dealing
+            } else {
+                CallPhase::Gathering
+            },
+        });
+
+        frames
+    }
+
+    fn is_finished(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let Some((range, position)) = self.ranges.last() else {
+            return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] });
+        };
+
+        let mut variables = vec![
+            ("lo", range.start.to_string()),
+            ("hi", range.end.to_string()),
+            ("d", position.to_string()),
+        ];
+        let line = if range.len() <= 1 {
+            1
+        } else if self.dealt < range.len() {
+            let string = &self.list[range.start + self.dealt].value;
+            variables.push(("s", format!("{string:?}")));
+            variables.push(("s[d]", describe_character(character_at(string, *position))));
+            3
+        } else {
+            4
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn get_character(&self) -> Option<usize> {
+        self.ranges.last().map(|(_, position)| *position)
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<String>>>) {
+        self.list = list.into_iter().flatten().collect();
+        self.ranges = vec![(0..self.list.len(), 0)];
+        self.callers = vec![];
+        self.buckets = vec![vec![]; 257];
+        self.dealt = 0;
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    // The buckets that have anything in them are shown after the elements
+    // before the range, so the first of them is list 1
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let Some((range, position)) = self.ranges.last().cloned() else {
+            return;
+        };
+
+        if range.len() <= 1 {
+            self.ranges.pop();
+            return;
+        }
+
+        // Deal out one element at a time
+        if self.dealt < range.len() {
+            // The elements still to be dealt are shown after the buckets
+            let filled = self.buckets.iter().filter(|bucket| !bucket.is_empty()).count();
+            self.accesses.compare_aside((1 + filled, 0));
+
+            let element = self.list[range.start + self.dealt].clone();
+            let bucket = Self::bucket_of(&element, position);
+            self.buckets[bucket].push(element);
+            self.dealt += 1;
+
+            let shown = 1 + self.buckets[..bucket].iter().filter(|bucket| !bucket.is_empty()).count();
+            self.accesses.write((shown, self.buckets[bucket].len() - 1));
+            return;
+        }
+
+        // Then gather the buckets back up, and sort each of them by the next
+        // character. The strings that have ended are all equal, so they're
+        // already sorted.
+        self.ranges.pop();
+        let mut start = range.start;
+        let mut next_ranges = vec![];
+        let mut shown = 1;
+        for bucket in &mut self.buckets {
+            let end = start + bucket.len();
+            if bucket.len() > 1 && character_at(&bucket[0].value, position).is_some() {
+                next_ranges.push((start..end, position + 1));
+            }
+            if !bucket.is_empty() {
+                self.accesses.reads.extend((0..bucket.len()).map(|index| (shown, index)));
+                shown += 1;
+            }
+
+            self.list.splice(start..end, bucket.drain(..));
+            start = end;
+        }
+        self.callers.retain(|(_, pending)| *pending <= self.ranges.len());
+        self.callers.push((range.clone(), self.ranges.len()));
+        self.ranges.extend(next_ranges.into_iter().rev());
+        self.dealt = 0;
+        self.accesses.writes = range.map(|index| self.position(index)).collect();
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
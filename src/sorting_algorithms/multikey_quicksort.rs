@@ -0,0 +1,282 @@
+use super::{character_at, describe_character, Accesses, CallFrame, CallPhase, Element, Highlight, Pseudocode, SortingAlgorithm};
+
+use std::{ops::Range, time};
+
+const PSEUDOCODE: &[&str] = &[
+    "multikey_quicksort(a, lo, hi, d):",
+    "  if hi - lo <= 1: return",
+    "  pivot = a[middle][d]; swap a[middle] to the front",
+    "  lt = lo; i = lo + 1; gt = hi",
+    "  while i < gt:",
+    "    if a[i][d] < pivot: swap a[lt], a[i]; lt += 1; i += 1",
+    "    elif a[i][d] > pivot: gt -= 1; swap a[i], a[gt]",
+    "    else: i += 1",
+    "  multikey_quicksort(a, lo, lt, d)",
+    "  if pivot isn't the end: multikey_quicksort(a, lt, gt, d + 1)",
+    "  multikey_quicksort(a, gt, hi, d)",
+];
+
+/// Bentley and Sedgewick's multikey quicksort, a three-way quicksort on one
+/// character at a time. The range is split into the strings whose character
+/// at the current position is smaller than the pivot's, equal to it and
+/// bigger than it. The equal part moves on to the next character, and the
+/// others are split again at the same one.
+#[derive(Clone)]
+pub struct MultikeyQuicksort {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<String>>,
+    // The ranges left to sort and the character position to sort them by,
+    // where the last one is sorted next
+    ranges: Vec<(Range<usize>, usize)>,
+    // The ranges that have been partitioned and are waiting on their parts
+    // to be sorted, outermost first, with their character position and how
+    // many ranges were left to sort before their parts were added
+    callers: Vec<(Range<usize>, usize, usize)>,
+    partition: Option<Partition>,
+    accesses: Accesses,
+}
+
+/// A three-way partition of the last range. Everything before `less_end` is
+/// smaller than the pivot, everything from `greater_start` on is bigger, and
+/// the index being compared is in between.
+#[derive(Clone, Copy, Debug)]
+struct Partition {
+    pivot: Option<u8>,
+    less_end: usize,
+    compared: usize,
+    greater_start: usize,
+}
+
+impl Default for MultikeyQuicksort {
+    fn default() -> Self {
+        let mut multikey_quicksort = Self {
+            name: "Multikey Quicksort",
+            default_delay: time::Duration::from_millis(80),
+            list: vec![],
+            ranges: vec![],
+            callers: vec![],
+            partition: None,
+            accesses: Accesses::default(),
+        };
+        multikey_quicksort.set_list(vec![Element::numbered(16)]);
+
+        multikey_quicksort
+    }
+}
+
+impl SortingAlgorithm<String> for MultikeyQuicksort {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<String>>>, Vec<(usize, usize)>) {
+        let highlights = match self.partition {
+            Some(partition) if partition.compared < partition.greater_start => {
+                vec![(0, partition.less_end), (0, partition.compared)]
+            },
+            _ => vec![],
+        };
+
+        (vec![self.list.clone()], highlights)
+    }
+
+    fn get_groups(&self) -> Vec<((usize, usize), usize)> {
+        let Some(partition) = self.partition else {
+            return vec![];
+        };
+        let Some((range, _)) = self.ranges.last() else {
+            return vec![];
+        };
+
+        // Color the smaller, equal and bigger parts found so far
+        let less = range.start..partition.less_end;
+        let equal = partition.less_end..partition.compared;
+        let greater = partition.greater_start..range.end;
+
+        [less, equal, greater]
+            .into_iter()
+            .enumerate()
+            .flat_map(|(group, part)| part.map(move |index| ((0, index), group * 4 + 1)))
+            .collect()
+    }
+
+    fn get_marks(&self) -> Vec<((usize, usize), Highlight)> {
+        match self.ranges.last() {
+            Some((range, _)) if !range.is_empty() => vec![
+                ((0, range.start), Highlight::Boundary),
+                ((0, range.end - 1), Highlight::Boundary),
+            ],
+            _ => vec![],
+        }
+    }
+
+    // A caller is done once every range added after it has been sorted
+    fn get_call_stack(&self) -> Vec<CallFrame> {
+        let Some((range, position)) = self.ranges.last() else {
+            return vec![];
+        };
+
+        let callers = self.callers.iter().take_while(|(.., pending)| *pending < self.ranges.len()).collect::<Vec<_>>();
+        let mut frames = callers
+            .iter()
+            .enumerate()
+            .map(|(depth, (caller, caller_position, _))| {
+                let (part, part_position) = callers.get(depth + 1).map_or((range, position), |(part, part_position, _)| (part, part_position));
+                let phase = if part_position > caller_position {
+                    CallPhase::SortingEqualPart
+                } else if part.start == caller.start {
+                    CallPhase::SortingSmallerPart
+                } else {
+                    CallPhase::SortingBiggerPart
+                };
+
+                CallFrame { range: caller.clone(), phase }
+            })
+            .collect::<Vec<_>>();
+        frames.push(CallFrame {
+            range: range.clone(),
+            phase: match self.partition {
+                Some(_) => CallPhase::Partitioning,
+                None if range.len() <= 1 => CallPhase::BaseCase,
+                None => CallPhase::PickingPivot,
+            },
+        });
+
+        frames
+    }
+
+    fn is_finished(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let Some((range, position)) = self.ranges.last() else {
+            return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] });
+        };
+
+        let mut variables = vec![
+            ("lo", range.start.to_string()),
+            ("hi", range.end.to_string()),
+            ("d", position.to_string()),
+        ];
+        let Some(partition) = self.partition else {
+            let line = if range.len() <= 1 { 1 } else { 2 };
+            return Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables });
+        };
+
+        variables.extend([
+            ("pivot", describe_character(partition.pivot)),
+            ("lt", partition.less_end.to_string()),
+            ("i", partition.compared.to_string()),
+            ("gt", partition.greater_start.to_string()),
+        ]);
+        let line = if partition.compared < partition.greater_start {
+            let element = &self.list[partition.compared];
+            let character = character_at(&element.value, *position);
+            variables.push(("a[i][d]", describe_character(character)));
+
+            let mut ordering = character.cmp(&partition.pivot);
+            if element.order.descending {
+                ordering = ordering.reverse();
+            }
+            match ordering {
+                std::cmp::Ordering::Less => 5,
+                std::cmp::Ordering::Greater => 6,
+                std::cmp::Ordering::Equal => 7,
+            }
+        } else {
+            8
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn get_character(&self) -> Option<usize> {
+        self.ranges.last().map(|(_, position)| *position)
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<String>>>) {
+        self.list = list.into_iter().flatten().collect();
+        self.ranges = vec![(0..self.list.len(), 0)];
+        self.callers = vec![];
+        self.partition = None;
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let Some((range, position)) = self.ranges.last().cloned() else {
+            return;
+        };
+
+        let Some(partition) = &mut self.partition else {
+            if range.len() <= 1 {
+                self.ranges.pop();
+                return;
+            }
+
+            // The middle string's character is the pivot, which is moved to
+            // the front
+            let middle = range.start + range.len() / 2;
+            self.list.swap(range.start, middle);
+            self.accesses.swap((0, range.start), (0, middle));
+            self.accesses.read((0, range.start));
+            self.partition = Some(Partition {
+                pivot: character_at(&self.list[range.start].value, position),
+                less_end: range.start,
+                compared: range.start + 1,
+                greater_start: range.end,
+            });
+            return;
+        };
+
+        if partition.compared < partition.greater_start {
+            let element = &self.list[partition.compared];
+            let character = character_at(&element.value, position);
+            self.accesses.compare_aside((0, partition.compared));
+            let mut ordering = character.cmp(&partition.pivot);
+            if element.order.descending {
+                ordering = ordering.reverse();
+            }
+            match ordering {
+                std::cmp::Ordering::Less => {
+                    self.list.swap(partition.less_end, partition.compared);
+                    self.accesses.swap((0, partition.less_end), (0, partition.compared));
+                    partition.less_end += 1;
+                    partition.compared += 1;
+                },
+                std::cmp::Ordering::Equal => partition.compared += 1,
+                std::cmp::Ordering::Greater => {
+                    partition.greater_start -= 1;
+                    self.list.swap(partition.compared, partition.greater_start);
+                    self.accesses.swap((0, partition.compared), (0, partition.greater_start));
+                },
+            }
+            return;
+        }
+
+        // The equal part only needs sorting further if its strings haven't
+        // ended
+        let Partition { pivot, less_end, greater_start, .. } = *partition;
+        self.partition = None;
+        self.ranges.pop();
+        self.callers.retain(|(.., pending)| *pending <= self.ranges.len());
+        self.callers.push((range.clone(), position, self.ranges.len()));
+        self.ranges.push((greater_start..range.end, position));
+        if pivot.is_some() {
+            self.ranges.push((less_end..greater_start, position + 1));
+        }
+        self.ranges.push((range.start..less_end, position));
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
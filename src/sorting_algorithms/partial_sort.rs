@@ -0,0 +1,217 @@
+use super::{Accesses, Element, Goal, Highlight, Pseudocode, SortingAlgorithm};
+
+use std::time;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "partial_sort(a, k):",
+    "  for node from k / 2 down to 0:",
+    "    sift_down(node, k + 1)",
+    "  for i in k + 1..len(a):",
+    "    if a[i] < a[0]:",
+    "      swap a[i], a[0]; sift_down(0, k + 1)",
+    "  for end from k + 1 down to 2:",
+    "    swap a[0], a[end - 1]; sift_down(0, end - 1)",
+    "",
+    "sift_down(node, end):",
+    "  child = the larger child of node",
+    "  if a[child] > a[node]: swap them; sift_down(child, end)",
+];
+
+/// Sorts only the list up to the target index, the way C++'s `partial_sort`
+/// does. The front of the list is made into a max heap, every later element
+/// that is smaller than the top of the heap replaces it, and then the heap is
+/// sorted.
+#[derive(Clone)]
+pub struct PartialSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<T>>,
+    target: usize,
+    // The element being sifted down the heap and the end of the heap, if one
+    // is being sifted
+    sifting: Option<(usize, usize)>,
+    phase: Phase,
+    accesses: Accesses,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Phase {
+    /// Making the front of the list into a heap, with the given number of
+    /// nodes left to sift down.
+    Heapify(usize),
+    /// Comparing the element at the index against the top of the heap.
+    Scan(usize),
+    /// Moving the top of the heap to the back of it, with the heap ending at
+    /// the index.
+    Extract(usize),
+    Done,
+}
+
+impl<T: Item> PartialSort<T> {
+    fn restart(&mut self, list: Vec<Element<T>>) {
+        self.target = self.target.min(list.len().saturating_sub(1));
+        self.sifting = None;
+        self.phase = if list.is_empty() { Phase::Done } else { Phase::Heapify(self.heap_size() / 2) };
+        self.list = list;
+    }
+
+    const fn heap_size(&self) -> usize {
+        self.target + 1
+    }
+}
+
+impl<T: Item> Default for PartialSort<T> {
+    fn default() -> Self {
+        let mut partial_sort = Self {
+            name: "Partial Sort",
+            default_delay: time::Duration::from_millis(80),
+            list: vec![],
+            target: 7,
+            sifting: None,
+            phase: Phase::Done,
+            accesses: Accesses::default(),
+        };
+        partial_sort.restart(Element::numbered(16));
+
+        partial_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for PartialSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let highlights = if let Some((node, end)) = self.sifting {
+            [node, 2 * node + 1, 2 * node + 2].into_iter().filter(|index| *index < end).collect()
+        } else if let Phase::Scan(index) = self.phase {
+            [0, index].into_iter().filter(|index| *index < self.list.len()).collect()
+        } else {
+            vec![]
+        };
+
+        (vec![self.list.clone()], highlights.into_iter().map(|index| (0, index)).collect())
+    }
+
+    // Each element taken off the top of the heap is put where it belongs
+    fn get_marks(&self) -> Vec<((usize, usize), Highlight)> {
+        let sorted = match self.phase {
+            Phase::Extract(end) => end..self.heap_size(),
+            Phase::Done if !self.list.is_empty() => 0..self.heap_size(),
+            _ => 0..0,
+        };
+
+        sorted.map(|index| ((0, index), Highlight::Final)).collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Done)
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let mut variables = vec![("k", self.target.to_string())];
+        let line = match self.phase {
+            Phase::Heapify(left) => {
+                variables.push(("node", left.to_string()));
+                if left == 0 { 1 } else { 2 }
+            },
+            Phase::Scan(index) => {
+                variables.push(("i", index.to_string()));
+                if index >= self.list.len() { 3 } else { 4 }
+            },
+            Phase::Extract(end) => {
+                variables.push(("end", end.to_string()));
+                if end <= 1 { 6 } else { 7 }
+            },
+            Phase::Done => return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables }),
+        };
+
+        // Sifting down is a call of its own, with variables of its own
+        if let Some((node, end)) = self.sifting {
+            return Some(Pseudocode {
+                lines: PSEUDOCODE,
+                line: Some(11),
+                variables: vec![("node", node.to_string()), ("end", end.to_string())],
+            });
+        }
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn get_goal(&self) -> Goal {
+        Goal::SortedUpTo(self.target)
+    }
+
+    fn set_target(&mut self, target: usize) {
+        self.target = target;
+        self.restart(self.list.clone());
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.restart(list.into_iter().flatten().collect());
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+
+        // Sifting takes a step per level of the heap
+        if let Some((node, end)) = self.sifting {
+            let mut largest = node;
+            for child in [2 * node + 1, 2 * node + 2].into_iter().filter(|child| *child < end) {
+                self.accesses.compare((0, child), (0, largest));
+                if self.list[child] > self.list[largest] {
+                    largest = child;
+                }
+            }
+
+            self.sifting = if largest == node {
+                None
+            } else {
+                self.list.swap(node, largest);
+                self.accesses.swap((0, node), (0, largest));
+                Some((largest, end))
+            };
+            return;
+        }
+
+        let heap_size = self.heap_size();
+        match self.phase {
+            Phase::Heapify(0) => self.phase = Phase::Scan(heap_size),
+            Phase::Heapify(left) => {
+                self.sifting = Some((left - 1, heap_size));
+                self.phase = Phase::Heapify(left - 1);
+            },
+            Phase::Scan(index) if index >= self.list.len() => self.phase = Phase::Extract(heap_size),
+            Phase::Scan(index) => {
+                self.accesses.compare((0, index), (0, 0));
+                if self.list[index] < self.list[0] {
+                    self.list.swap(index, 0);
+                    self.accesses.swap((0, index), (0, 0));
+                    self.sifting = Some((0, heap_size));
+                }
+                self.phase = Phase::Scan(index + 1);
+            },
+            Phase::Extract(end) if end <= 1 => self.phase = Phase::Done,
+            Phase::Extract(end) => {
+                self.list.swap(0, end - 1);
+                self.accesses.swap((0, 0), (0, end - 1));
+                self.sifting = Some((0, end - 1));
+                self.phase = Phase::Extract(end - 1);
+            },
+            Phase::Done => {},
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
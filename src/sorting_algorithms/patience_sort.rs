@@ -0,0 +1,162 @@
+use super::{Accesses, Element, Pseudocode, SortingAlgorithm};
+
+use std::time;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "patience_sort(a):",
+    "  for each x in a:",
+    "    pile = leftmost pile whose top >= x",
+    "    put x on pile, or on a new pile",
+    "  while any pile has cards:",
+    "    move the smallest top to the output",
+];
+
+/// Deals the elements onto piles like a game of patience, where each element
+/// goes on the leftmost pile whose top is no smaller than it. Every pile ends
+/// up sorted from the top down, so the piles are then merged together by
+/// repeatedly taking the smallest top.
+#[derive(Clone)]
+pub struct PatienceSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    // The elements still to be dealt, or the merged output once dealing is
+    // done
+    list: Vec<Element<T>>,
+    piles: Vec<Vec<Element<T>>>,
+    dealing: bool,
+    accesses: Accesses,
+}
+
+impl<T: Item> Default for PatienceSort<T> {
+    fn default() -> Self {
+        Self {
+            name: "Patience Sort",
+            default_delay: time::Duration::from_millis(100),
+            list: Element::numbered(16),
+            piles: vec![],
+            dealing: true,
+            accesses: Accesses::default(),
+        }
+    }
+}
+
+impl<T: Item> PatienceSort<T> {
+    /// The pile with the smallest top, picking the leftmost on ties.
+    fn smallest_pile(&self) -> Option<usize> {
+        self.piles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pile)| pile.last().map(|top| (top, index)))
+            .min()
+            .map(|(_, index)| index)
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for PatienceSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let mut lists = vec![self.list.clone()];
+        lists.extend(self.piles.iter().cloned());
+
+        let highlights = if self.dealing {
+            if self.list.is_empty() { vec![] } else { vec![(0, 0)] }
+        } else {
+            self.smallest_pile()
+                .map(|pile| vec![(pile + 1, self.piles[pile].len() - 1)])
+                .unwrap_or_default()
+        };
+
+        (lists, highlights)
+    }
+
+    fn is_finished(&self) -> bool {
+        !self.dealing && self.smallest_pile().is_none()
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let piles = ("piles", self.piles.len().to_string());
+        let (line, variables) = if self.dealing {
+            match self.list.first() {
+                Some(element) => {
+                    let pile = self.piles.partition_point(|pile| pile.last().is_some_and(|top| top < element));
+                    (2, vec![("x", element.value.to_string()), ("pile", pile.to_string()), piles])
+                },
+                None => (1, vec![piles]),
+            }
+        } else {
+            let Some(pile) = self.smallest_pile() else {
+                return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] });
+            };
+            let top = self.piles[pile].last().unwrap();
+            (5, vec![("pile", pile.to_string()), ("top", top.value.to_string()), piles])
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.list = list.into_iter().flatten().collect();
+        self.piles = vec![];
+        self.dealing = true;
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    // The piles are shown after the list, so pile `p` is list `p + 1`
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        if self.dealing {
+            if self.list.is_empty() {
+                self.dealing = false;
+                return;
+            }
+
+            // The tops of the piles go up from left to right, so the pile can
+            // be binary searched for
+            let element = self.list.remove(0);
+            self.accesses.read((0, 0));
+            let (mut pile, mut end) = (0, self.piles.len());
+            while pile < end {
+                let middle = pile.midpoint(end);
+                let top = self.piles[middle].len() - 1;
+                self.accesses.compare_aside((middle + 1, top));
+                if self.piles[middle][top] < element {
+                    pile = middle + 1;
+                } else {
+                    end = middle;
+                }
+            }
+            if pile == self.piles.len() {
+                self.piles.push(vec![]);
+            }
+            self.piles[pile].push(element);
+            self.accesses.write((pile + 1, self.piles[pile].len() - 1));
+        } else if let Some(pile) = self.smallest_pile() {
+            // Finding the smallest top compares the tops against each other
+            let tops = (0..self.piles.len()).filter(|pile| !self.piles[*pile].is_empty()).collect::<Vec<_>>();
+            self.accesses.reads.extend(tops.iter().map(|pile| (pile + 1, self.piles[*pile].len() - 1)));
+            self.accesses.comparisons += tops.len() - 1;
+
+            let element = self.piles[pile].pop().unwrap();
+            self.list.push(element);
+            self.accesses.write((0, self.list.len() - 1));
+
+            if self.piles.iter().all(Vec::is_empty) {
+                self.piles = vec![];
+            }
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
@@ -0,0 +1,165 @@
+use super::{get_available_algorithms, Accesses, Element, Highlight, HighlightedList, Pseudocode, SortingAlgorithm};
+
+use std::time;
+use crate::{items::Item, order::Order};
+
+/// Any of the sorting algorithms run on every row of a grid at once, each
+/// row being a list of its own. Every step steps all of the rows.
+pub struct RowSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: String,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    rows: Vec<Box<dyn SortingAlgorithm<T>>>,
+    target: usize,
+    seed: u64,
+    // Where each of every row's lists started before the last step
+    offsets_before: Vec<Vec<usize>>,
+}
+
+impl<T: Item> RowSort<T> {
+    /// Sort every row with the available algorithm of the name.
+    pub fn new(name: &str) -> Self {
+        let algorithm = Self::make_row(name);
+
+        Self {
+            name: name.to_string(),
+            default_delay: algorithm.get_delay(),
+            rows: vec![],
+            target: 0,
+            seed: 0,
+            offsets_before: vec![],
+        }
+    }
+
+    /// Where each of the lists a row's algorithm has it split into starts in
+    /// the row.
+    fn offsets(sublists: &[Vec<Element<T>>]) -> Vec<usize> {
+        sublists
+            .iter()
+            .scan(0, |offset, sublist| {
+                let start = *offset;
+                *offset += sublist.len();
+                Some(start)
+            })
+            .collect()
+    }
+
+    fn make_row(name: &str) -> Box<dyn SortingAlgorithm<T>> {
+        get_available_algorithms()
+            .into_iter()
+            .find(|algorithm| algorithm.get_name() == name)
+            .expect("rows are only sorted by the available algorithms")
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for RowSort<T> {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    // Whatever lists a row's algorithm has it split into are put back
+    // together into the row
+    fn get_list(&self) -> HighlightedList<T> {
+        let mut list = vec![];
+        let mut highlights = vec![];
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let (sublists, row_highlights) = row.get_list();
+            let offsets = Self::offsets(&sublists);
+
+            highlights.extend(row_highlights.into_iter().map(|(list_index, index)| (row_index, offsets[list_index] + index)));
+            list.push(sublists.concat());
+        }
+
+        (list, highlights)
+    }
+
+    fn get_marks(&self) -> Vec<((usize, usize), Highlight)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row_index, row)| {
+                let offsets = Self::offsets(&row.get_list().0);
+                row.get_marks()
+                    .into_iter()
+                    .map(move |((list_index, index), highlight)| ((row_index, offsets[list_index] + index), highlight))
+            })
+            .collect()
+    }
+
+    // Every row runs the same code, so the top row stands in for them all
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        self.rows.first().and_then(|row| row.get_pseudocode())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.rows.iter().all(|row| row.is_finished())
+    }
+
+    fn set_target(&mut self, target: usize) {
+        self.target = target;
+        for row in &mut self.rows {
+            row.set_target(target);
+        }
+    }
+
+    fn moves_elements(&self) -> bool {
+        Self::make_row(&self.name).moves_elements()
+    }
+
+    fn supports_order(&self, order: Order) -> bool {
+        Self::make_row(&self.name).supports_order(order)
+    }
+
+    // Each row gets a seed of its own, so that the rows don't all make the
+    // same random choices
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        for (row_index, row) in self.rows.iter_mut().enumerate() {
+            row.set_seed(seed.wrapping_add(row_index as u64));
+        }
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.rows = list
+            .into_iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                let mut algorithm = Self::make_row(&self.name);
+                algorithm.set_target(self.target);
+                algorithm.set_seed(self.seed.wrapping_add(row_index as u64));
+                algorithm.set_list(vec![row]);
+                algorithm
+            })
+            .collect();
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.offsets_before = self.rows.iter().map(|row| Self::offsets(&row.get_list().0)).collect();
+        for row in &mut self.rows {
+            row.step();
+        }
+    }
+
+    // Each row's accesses are moved into the row, going by where its lists
+    // were before the step for reads and after it for writes
+    fn get_accesses(&self) -> Accesses {
+        let mut accesses = Accesses::default();
+        for (row_index, (row, before)) in self.rows.iter().zip(&self.offsets_before).enumerate() {
+            let row_accesses = row.get_accesses();
+            let after = Self::offsets(&row.get_list().0);
+
+            accesses.reads.extend(row_accesses.reads.into_iter().map(|(list_index, index)| (row_index, before[list_index] + index)));
+            accesses.writes.extend(row_accesses.writes.into_iter().map(|(list_index, index)| (row_index, after[list_index] + index)));
+            accesses.writes_aside += row_accesses.writes_aside;
+            accesses.comparisons += row_accesses.comparisons;
+        }
+
+        accesses
+    }
+}
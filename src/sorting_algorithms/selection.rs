@@ -0,0 +1,354 @@
+use super::{
+    inner_sort::{InnerSort, InnerSorter},
+    Accesses,
+    CallFrame,
+    CallPhase,
+    Element,
+    Goal,
+    Highlight,
+    Pseudocode,
+    SortingAlgorithm,
+};
+
+use std::{ops::Range, time};
+use rand::prelude::*;
+use crate::items::Item;
+
+/// How a selection algorithm picks the pivot to partition around.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PivotRule {
+    /// A random element, which is fast on average but can be unlucky.
+    Random,
+    /// The median of the medians of groups of five, which always throws away
+    /// a good part of the range.
+    MedianOfMedians,
+    /// Random pivots until the partitions stop shrinking fast enough, then
+    /// median of medians.
+    Introspective,
+}
+
+const PSEUDOCODE: &[&str] = &[
+    "select(lo, hi, k):",
+    "  while hi - lo > 1:",
+    "    if careful:",
+    "      for each group of five in lo..hi:",
+    "        insertion sort the group",
+    "        move its median to the front",
+    "      pivot = select(the medians)",
+    "    else:",
+    "      pivot = random index in lo..hi",
+    "    swap a[pivot] to the end",
+    "    store = lo",
+    "    for i in lo..hi - 1:",
+    "      if a[i] < pivot:",
+    "        swap a[store], a[i]; store += 1",
+    "    swap a[store], a[hi - 1]",
+    "    if store == k: return",
+    "    keep the side with k in it",
+];
+
+/// Quickselect and its relatives. The range is partitioned around a pivot,
+/// and only the side with the target index in it is kept, until the pivot
+/// lands on the target.
+#[derive(Clone)]
+pub struct Selection<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+    pivot_rule: PivotRule,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<T>>,
+    target: usize,
+    // The selections being done, where the last one is being worked on. Median
+    // of medians needs a selection of its own to find its pivot.
+    frames: Vec<Frame>,
+    rng: StdRng,
+    accesses: Accesses,
+}
+
+#[derive(Clone, Debug)]
+struct Frame {
+    range: Range<usize>,
+    target: usize,
+    // Whether pivots are picked with median of medians
+    careful: bool,
+    // How many more partitions an introspective selection gets before it
+    // becomes careful
+    budget: usize,
+    phase: Phase,
+}
+
+#[derive(Clone, Debug)]
+enum Phase {
+    PickPivot,
+    /// Sorting the given group of five to find its median.
+    SortGroup(usize, InnerSorter),
+    /// Waiting on the median of the given number of medians to be selected.
+    AwaitMedian(usize),
+    /// Partitioning around the pivot at the end of the range. Holds where the
+    /// next smaller element goes and the index being compared.
+    Partition(usize, usize),
+}
+
+impl<T: Item> Selection<T> {
+    pub fn quickselect() -> Self {
+        Self::new("Quickselect", PivotRule::Random)
+    }
+
+    pub fn introselect() -> Self {
+        Self::new("Introselect", PivotRule::Introspective)
+    }
+
+    pub fn median_of_medians() -> Self {
+        Self::new("Median of Medians", PivotRule::MedianOfMedians)
+    }
+
+    fn new(name: &'static str, pivot_rule: PivotRule) -> Self {
+        let mut selection = Self {
+            name,
+            default_delay: time::Duration::from_millis(80),
+            pivot_rule,
+            list: vec![],
+            target: 7,
+            frames: vec![],
+            rng: StdRng::seed_from_u64(0),
+            accesses: Accesses::default(),
+        };
+        selection.restart(Element::numbered(16));
+
+        selection
+    }
+
+    fn restart(&mut self, list: Vec<Element<T>>) {
+        self.target = self.target.min(list.len().saturating_sub(1));
+        self.frames = vec![Frame {
+            range: 0..list.len(),
+            target: self.target,
+            careful: self.pivot_rule == PivotRule::MedianOfMedians,
+            budget: 2 * list.len().max(1).ilog2() as usize,
+            phase: Phase::PickPivot,
+        }];
+        self.list = list;
+    }
+
+    /// The range of the group of five being sorted.
+    fn group(range: &Range<usize>, group: usize) -> Range<usize> {
+        let start = range.start + 5 * group;
+
+        start..(start + 5).min(range.end)
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for Selection<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let highlights = match self.frames.last() {
+            Some(Frame { range, phase: Phase::SortGroup(group, sorter), .. }) => {
+                let group = Self::group(range, *group);
+
+                sorter
+                    .get_highlights(&self.list[group.clone()])
+                    .into_iter()
+                    .map(|index| index + group.start)
+                    .collect()
+            },
+            Some(Frame { phase: Phase::Partition(store, compared), .. }) => vec![*store, *compared],
+            _ => vec![],
+        };
+
+        (vec![self.list.clone()], highlights.into_iter().map(|index| (0, index)).collect())
+    }
+
+    // The pivot waits at the end of the range while the range is
+    // partitioned around it
+    fn get_marks(&self) -> Vec<((usize, usize), Highlight)> {
+        let Some(frame) = self.frames.last().filter(|frame| !frame.range.is_empty()) else {
+            return vec![];
+        };
+
+        let mut marks = vec![((0, frame.range.start), Highlight::Boundary), ((0, frame.range.end - 1), Highlight::Boundary)];
+        if let Phase::Partition(..) = frame.phase {
+            marks.push(((0, frame.range.end - 1), Highlight::Pivot));
+        }
+
+        marks
+    }
+
+    fn get_call_stack(&self) -> Vec<CallFrame> {
+        self.frames
+            .iter()
+            .map(|frame| CallFrame {
+                range: frame.range.clone(),
+                phase: match frame.phase {
+                    Phase::PickPivot => CallPhase::PickingPivot,
+                    Phase::SortGroup(..) => CallPhase::SortingGroup,
+                    Phase::AwaitMedian(_) => CallPhase::SelectingMedian,
+                    Phase::Partition(..) => CallPhase::Partitioning,
+                },
+            })
+            .collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let Some(frame) = self.frames.last() else {
+            return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] });
+        };
+
+        let mut variables = vec![
+            ("lo", frame.range.start.to_string()),
+            ("hi", frame.range.end.to_string()),
+            ("k", frame.target.to_string()),
+        ];
+        if self.pivot_rule != PivotRule::Random {
+            variables.push(("careful", frame.careful.to_string()));
+        }
+        let line = match frame.phase {
+            Phase::PickPivot if frame.range.len() <= 1 => 1,
+            Phase::PickPivot if frame.careful => 3,
+            Phase::PickPivot => 8,
+            Phase::SortGroup(group, _) => {
+                variables.push(("group", group.to_string()));
+                4
+            },
+            Phase::AwaitMedian(_) => 9,
+            Phase::Partition(store, compared) => {
+                variables.push(("store", store.to_string()));
+                variables.push(("i", compared.to_string()));
+                variables.push(("pivot", self.list[frame.range.end - 1].value.to_string()));
+                if compared < frame.range.end - 1 { 12 } else { 14 }
+            },
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn get_goal(&self) -> Goal {
+        Goal::Selected(self.target)
+    }
+
+    fn set_target(&mut self, target: usize) {
+        self.target = target;
+        self.restart(self.list.clone());
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.restart(list.into_iter().flatten().collect());
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let Some(frame) = self.frames.last_mut() else {
+            return;
+        };
+
+        match &mut frame.phase {
+            Phase::PickPivot => {
+                if frame.range.len() <= 1 {
+                    self.frames.pop();
+                } else if frame.careful {
+                    // Small ranges are sorted outright
+                    let group = Self::group(&frame.range, 0);
+                    frame.phase = Phase::SortGroup(0, InnerSorter::new(InnerSort::Insertion, group.len()));
+                } else {
+                    let pivot = self.rng.gen_range(frame.range.clone());
+                    self.list.swap(pivot, frame.range.end - 1);
+                    self.accesses.swap((0, pivot), (0, frame.range.end - 1));
+                    frame.phase = Phase::Partition(frame.range.start, frame.range.start);
+                }
+            },
+            Phase::SortGroup(group_index, sorter) => {
+                let group = Self::group(&frame.range, *group_index);
+                sorter.step(&mut self.list[group.clone()], &mut self.accesses, (0, group.start));
+
+                if !sorter.is_finished(&self.list[group.clone()]) {
+                    return;
+                }
+                if frame.range.len() <= 5 {
+                    self.frames.pop();
+                    return;
+                }
+
+                // Move the median to the front, next to the ones before it
+                let median = group.start + (group.len() - 1) / 2;
+                self.list.swap(frame.range.start + *group_index, median);
+                self.accesses.swap((0, frame.range.start + *group_index), (0, median));
+                *group_index += 1;
+
+                let next_group = Self::group(&frame.range, *group_index);
+                if next_group.is_empty() {
+                    let medians = *group_index;
+                    frame.phase = Phase::AwaitMedian(medians);
+
+                    let range = frame.range.start..frame.range.start + medians;
+                    self.frames.push(Frame {
+                        target: range.start + (medians - 1) / 2,
+                        range,
+                        careful: true,
+                        budget: 0,
+                        phase: Phase::PickPivot,
+                    });
+                } else {
+                    *sorter = InnerSorter::new(InnerSort::Insertion, next_group.len());
+                }
+            },
+            Phase::AwaitMedian(medians) => {
+                let pivot = frame.range.start + (*medians - 1) / 2;
+                self.list.swap(pivot, frame.range.end - 1);
+                self.accesses.swap((0, pivot), (0, frame.range.end - 1));
+                frame.phase = Phase::Partition(frame.range.start, frame.range.start);
+            },
+            Phase::Partition(store, compared) => {
+                let pivot = frame.range.end - 1;
+                if *compared < pivot {
+                    self.accesses.compare((0, *compared), (0, pivot));
+                    if self.list[*compared] < self.list[pivot] {
+                        self.list.swap(*store, *compared);
+                        self.accesses.swap((0, *store), (0, *compared));
+                        *store += 1;
+                    }
+                    *compared += 1;
+                    return;
+                }
+
+                let store = *store;
+                self.list.swap(store, pivot);
+                self.accesses.swap((0, store), (0, pivot));
+                if frame.target == store {
+                    self.frames.pop();
+                    return;
+                }
+
+                frame.range = if frame.target < store {
+                    frame.range.start..store
+                } else {
+                    store + 1..frame.range.end
+                };
+                frame.budget = frame.budget.saturating_sub(1);
+                if self.pivot_rule == PivotRule::Introspective && frame.budget == 0 {
+                    frame.careful = true;
+                }
+                frame.phase = Phase::PickPivot;
+            },
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
@@ -0,0 +1,179 @@
+use super::{height_floor, Accesses, Element, Layout, Pseudocode, SortingAlgorithm};
+
+use std::time;
+use rand::prelude::*;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "spaghetti_sort(a):",
+    "  cut a rod to the length of each element",
+    "  lower the bundle until every rod stands",
+    "  bring a hand down from above:",
+    "    if it touches the tallest rod:",
+    "      take it out, in front of the others",
+    "    else: lower the hand",
+];
+
+/// Cut a spaghetti rod to the length of each element, hold them all loosely
+/// and lower them onto a table so they stand level. Then bring a hand down
+/// from above. The first rod it touches is the tallest, so it is taken out
+/// and put in front of the ones taken out before it.
+#[derive(Clone)]
+pub struct SpaghettiSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    standing: Vec<Element<T>>,
+    // How far above the table each standing rod is
+    lifts: Vec<f32>,
+    taken_out: Vec<Element<T>>,
+    hand: Option<f32>,
+    // The height the rods are measured up from
+    base: f32,
+    // How far the rods or the hand move in one step
+    speed: f32,
+    rng: StdRng,
+    accesses: Accesses,
+}
+
+impl<T: Item> SpaghettiSort<T> {
+    fn rod_length(&self, element: &Element<T>) -> f32 {
+        element.height() - self.base
+    }
+}
+
+impl<T: Item> Default for SpaghettiSort<T> {
+    fn default() -> Self {
+        let mut spaghetti_sort = Self {
+            name: "Spaghetti Sort",
+            default_delay: time::Duration::from_millis(50),
+            standing: vec![],
+            lifts: vec![],
+            taken_out: vec![],
+            hand: None,
+            base: 0.0,
+            speed: 1.0,
+            rng: StdRng::seed_from_u64(0),
+            accesses: Accesses::default(),
+        };
+        spaghetti_sort.set_list(vec![Element::numbered(16)]);
+
+        spaghetti_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for SpaghettiSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        // The tallest rod is the one the hand is about to touch. Of equal rods
+        // the last is taken out first, which keeps them in order.
+        let highlights = self.standing
+            .iter()
+            .enumerate()
+            .max_by(|(a_index, a), (b_index, b)| a.cmp(b).then(a_index.cmp(b_index)))
+            .filter(|_| self.hand.is_some())
+            .map(|(index, _)| vec![(0, index)])
+            .unwrap_or_default();
+
+        (vec![self.standing.clone(), self.taken_out.clone()], highlights)
+    }
+
+    fn get_layout(&self) -> Layout {
+        let mut lifts = self.lifts.clone();
+        lifts.resize(self.standing.len() + self.taken_out.len(), 0.0);
+
+        Layout::Rods(lifts, self.hand)
+    }
+
+    // The hand is only lowered once the bundle has landed
+    fn is_finished(&self) -> bool {
+        self.hand.is_some() && self.standing.is_empty()
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let mut variables = vec![("taken out", self.taken_out.len().to_string())];
+        let Some(hand) = self.hand else {
+            let highest = self.lifts.iter().copied().fold(0.0, f32::max);
+            variables.push(("highest lift", format!("{highest:.1}")));
+            return Some(Pseudocode { lines: PSEUDOCODE, line: Some(2), variables });
+        };
+
+        let tallest = self.standing.iter().map(|element| self.rod_length(element)).reduce(f32::max);
+        variables.push(("hand", format!("{hand:.1}")));
+        let line = tallest.map(|tallest| {
+            variables.push(("tallest", format!("{tallest:.1}")));
+            if tallest >= hand { 5 } else { 6 }
+        });
+
+        Some(Pseudocode { lines: PSEUDOCODE, line, variables })
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        let list = list.into_iter().flatten().collect::<Vec<_>>();
+        self.base = height_floor(list.iter().map(Element::height));
+        let max = list.iter().map(|element| self.rod_length(element)).fold(0.0, f32::max);
+
+        self.lifts = list.iter().map(|_| self.rng.gen_range(0.0..=max * 0.5)).collect();
+        self.standing = list;
+        self.taken_out = vec![];
+        self.hand = None;
+        self.speed = (max / 30.0).max(0.5);
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    // The rods' lifts are beside the list, and finding the tallest rod
+    // compares every standing rod
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        let Some(hand) = self.hand else {
+            // Lower the bundle until every rod is standing on the table
+            for lift in self.lifts.iter_mut().filter(|lift| **lift > 0.0) {
+                *lift = (*lift - self.speed).max(0.0);
+                self.accesses.writes_aside += 1;
+            }
+
+            if self.lifts.iter().all(|lift| *lift == 0.0) {
+                let tallest = self.standing.iter().map(|element| self.rod_length(element)).fold(0.0, f32::max);
+                self.hand = Some(tallest + self.speed);
+                self.accesses.reads.extend((0..self.standing.len()).map(|index| (0, index)));
+                self.accesses.comparisons += self.standing.len().saturating_sub(1);
+            }
+            return;
+        };
+
+        self.accesses.reads.extend((0..self.standing.len()).map(|index| (0, index)));
+        self.accesses.comparisons += self.standing.len();
+        let tallest = self.standing
+            .iter()
+            .enumerate()
+            .max_by(|(a_index, a), (b_index, b)| a.cmp(b).then(a_index.cmp(b_index)))
+            .map(|(index, element)| (index, self.rod_length(element)));
+
+        match tallest {
+            Some((index, length)) if length >= hand => {
+                let element = self.standing.remove(index);
+                self.lifts.remove(index);
+                self.taken_out.insert(0, element);
+                self.accesses.write((1, 0));
+            },
+            Some(_) => self.hand = Some(hand - self.speed),
+            None => {},
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
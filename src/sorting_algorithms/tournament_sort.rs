@@ -0,0 +1,227 @@
+use super::{Accesses, Element, Highlight, Pseudocode, SortingAlgorithm, Tree, TreeNode};
+
+use std::time;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "tournament_sort(a):",
+    "  for node from the last match up to the root:",
+    "    winners[node] = smaller of its children",
+    "  for i in 0..len(a):",
+    "    a[i] = winners[root]; empty its leaf",
+    "    for node on its path up to the root:",
+    "      winners[node] = smaller of its children",
+];
+
+/// Plays the elements off against each other in a winner tree. The overall
+/// winner is written out, its leaf is emptied, and the matches on its path
+/// back up to the root are replayed to find the next winner.
+#[derive(Clone)]
+pub struct TournamentSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<T>>,
+    // The players, padded out to a power of two with empty leaves
+    leaves: Vec<Option<Element<T>>>,
+    // The leaf that won the match at each node, stored like a heap (the root
+    // is at 1, and the children of `n` are at `2n` and `2n + 1`)
+    winners: Vec<Option<usize>>,
+    phase: Phase,
+    accesses: Accesses,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Phase {
+    /// Playing the first round of matches, from the given node back to the
+    /// root.
+    Build(usize),
+    /// Writing out the winner, with the number of elements written so far.
+    Output(usize),
+    /// Replaying the matches at the given node and above, with the number of
+    /// elements written so far.
+    Replay(usize, usize),
+    Done,
+}
+
+impl<T: Item> TournamentSort<T> {
+    fn restart(&mut self, list: Vec<Element<T>>) {
+        let leaf_count = list.len().next_power_of_two();
+
+        self.leaves = list.iter().cloned().map(Some).collect();
+        self.leaves.resize(leaf_count, None);
+        self.winners = vec![None; leaf_count];
+        self.winners.extend((0..leaf_count).map(Some));
+        self.phase = if leaf_count > 1 { Phase::Build(leaf_count - 1) } else { Phase::Output(0) };
+        self.list = list;
+    }
+
+    /// Play the match at the node. The players are in the tree rather than
+    /// the list, so only the comparison and the winner written count.
+    fn play(&mut self, node: usize) {
+        let value = |winner: Option<usize>| winner.and_then(|leaf| self.leaves[leaf].as_ref());
+        let (left, right) = (self.winners[2 * node], self.winners[2 * node + 1]);
+
+        // Empty leaves always lose, and ties go to the left so that the sort
+        // is stable
+        self.winners[node] = match (value(left), value(right)) {
+            (Some(l), Some(r)) => {
+                self.accesses.comparisons += 1;
+                if r < l { right } else { left }
+            },
+            (None, Some(_)) => right,
+            _ => left,
+        };
+        self.accesses.writes_aside += 1;
+    }
+
+    fn winner(&self, node: usize) -> Option<&Element<T>> {
+        self.winners[node].and_then(|leaf| self.leaves[leaf].as_ref())
+    }
+}
+
+impl<T: Item> Default for TournamentSort<T> {
+    fn default() -> Self {
+        let mut tournament_sort = Self {
+            name: "Tournament Sort",
+            default_delay: time::Duration::from_millis(80),
+            list: vec![],
+            leaves: vec![],
+            winners: vec![],
+            phase: Phase::Done,
+            accesses: Accesses::default(),
+        };
+        tournament_sort.restart(Element::numbered(16));
+
+        tournament_sort
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for TournamentSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let highlights = match self.phase {
+            Phase::Output(written) | Phase::Replay(_, written) if written > 0 => vec![(0, written - 1)],
+            _ => vec![],
+        };
+
+        (vec![self.list.clone()], highlights)
+    }
+
+    // The winners are written out in order, so they never move again
+    fn get_marks(&self) -> Vec<((usize, usize), Highlight)> {
+        let written = match self.phase {
+            Phase::Build(_) => 0,
+            Phase::Output(written) | Phase::Replay(_, written) => written,
+            Phase::Done => self.list.len(),
+        };
+
+        (0..written).map(|index| ((0, index), Highlight::Final)).collect()
+    }
+
+    fn get_tree(&self) -> Option<Tree<T>> {
+        let leaf_count = self.leaves.len();
+        if self.list.is_empty() {
+            return None;
+        }
+
+        // The tree's nodes are numbered from 0, so they're one behind the heap
+        let nodes = (1..self.winners.len())
+            .map(|node| {
+                let has_children = node < leaf_count;
+
+                TreeNode {
+                    values: self.winner(node).map(|element| element.value.clone()).into_iter().collect(),
+                    left: has_children.then(|| 2 * node - 1),
+                    right: has_children.then_some(2 * node),
+                }
+            })
+            .collect();
+        let highlights = match self.phase {
+            Phase::Build(node) | Phase::Replay(node, _) => vec![(node - 1, Highlight::Compare)],
+            Phase::Output(_) => vec![(0, Highlight::Compare)],
+            Phase::Done => vec![],
+        };
+
+        Some(Tree {
+            nodes,
+            root: Some(0),
+            highlights,
+        })
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Done)
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let (line, node, written) = match self.phase {
+            Phase::Build(node) => (2, Some(node), None),
+            Phase::Output(written) => (4, None, Some(written)),
+            Phase::Replay(node, written) => (6, Some(node), Some(written)),
+            Phase::Done => return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] }),
+        };
+
+        let mut variables = vec![];
+        if let Some(written) = written {
+            variables.push(("i", written.to_string()));
+        }
+        if let Some(node) = node {
+            variables.push(("node", node.to_string()));
+        }
+        if let Some(winner) = self.winner(1) {
+            variables.push(("winners[root]", winner.value.to_string()));
+        }
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.restart(list.into_iter().flatten().collect());
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        match self.phase {
+            Phase::Build(node) => {
+                self.play(node);
+                self.phase = if node > 1 { Phase::Build(node - 1) } else { Phase::Output(0) };
+            },
+            Phase::Output(written) => {
+                let Some(leaf) = self.winners[1] else { return };
+                let Some(element) = self.leaves[leaf].take() else {
+                    self.phase = Phase::Done;
+                    return;
+                };
+
+                // The winner's leaf is emptied as it's written out
+                self.list[written] = element;
+                self.accesses.write((0, written));
+                self.accesses.writes_aside += 1;
+
+                // The leaves are stored after the internal nodes of the heap
+                let heap_index = self.leaves.len() + leaf;
+                let parent = heap_index / 2;
+                self.phase = if parent > 0 { Phase::Replay(parent, written + 1) } else { Phase::Done };
+            },
+            Phase::Replay(node, written) => {
+                self.play(node);
+                self.phase = if node > 1 { Phase::Replay(node / 2, written) } else { Phase::Output(written) };
+            },
+            Phase::Done => {},
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}
@@ -0,0 +1,202 @@
+use super::{Accesses, Element, Highlight, Pseudocode, SortingAlgorithm, Tree, TreeNode};
+
+use std::time;
+use crate::items::Item;
+
+const PSEUDOCODE: &[&str] = &[
+    "tree_sort(a):",
+    "  for i in 0..len(a):",
+    "    node = root",
+    "    while node has a child on a[i]'s side:",
+    "      node = that child",
+    "    hang a[i] off node",
+    "  for each node in order:",
+    "    a[written] = node; written += 1",
+];
+
+/// Inserts every element into a binary search tree, then writes the tree back
+/// over the list with an in-order traversal.
+#[derive(Clone)]
+pub struct TreeSort<T> {
+    // Statics, defaulted by the sorting algorithm and kept that way
+    name: &'static str,
+    default_delay: time::Duration,
+
+    // Mutables, these change as the sorting algorithm works.
+    list: Vec<Element<T>>,
+    nodes: Vec<Node<T>>,
+    phase: Phase,
+    accesses: Accesses,
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    element: Element<T>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum Phase {
+    /// Inserting the element at the index, currently comparing it against the
+    /// given node.
+    Insert(usize, usize),
+    /// Walking the tree in order. Holds the nodes whose left sides are being
+    /// walked, the node to walk next, the last node written back and the
+    /// number of elements written back.
+    Traverse(Vec<usize>, Option<usize>, Option<usize>, usize),
+    Done,
+}
+
+impl<T: Item> Default for TreeSort<T> {
+    fn default() -> Self {
+        Self {
+            name: "Tree Sort",
+            default_delay: time::Duration::from_millis(80),
+            list: Element::numbered(16),
+            nodes: vec![],
+            phase: Phase::Insert(0, 0),
+            accesses: Accesses::default(),
+        }
+    }
+}
+
+impl<T: Item> SortingAlgorithm<T> for TreeSort<T> {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_list(&self) -> (Vec<Vec<Element<T>>>, Vec<(usize, usize)>) {
+        let highlights = match self.phase {
+            Phase::Insert(index, _) if index < self.list.len() => vec![(0, index)],
+            Phase::Traverse(.., written) if written > 0 => vec![(0, written - 1)],
+            _ => vec![],
+        };
+
+        (vec![self.list.clone()], highlights)
+    }
+
+    fn get_tree(&self) -> Option<Tree<T>> {
+        let highlights = match &self.phase {
+            Phase::Insert(_, node) if *node < self.nodes.len() => vec![(*node, Highlight::Compare)],
+            Phase::Traverse(_, _, last, _) => last.iter().map(|node| (*node, Highlight::Compare)).collect(),
+            _ => vec![],
+        };
+
+        Some(Tree {
+            nodes: self.nodes
+                .iter()
+                .map(|node| TreeNode {
+                    values: vec![node.element.value.clone()],
+                    left: node.left,
+                    right: node.right,
+                })
+                .collect(),
+            root: if self.nodes.is_empty() { None } else { Some(0) },
+            highlights,
+        })
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Done)
+    }
+
+    fn get_pseudocode(&self) -> Option<Pseudocode> {
+        let (line, variables) = match &self.phase {
+            Phase::Insert(index, _) if *index == self.list.len() => (1, vec![("i", index.to_string())]),
+            Phase::Insert(index, node) => {
+                let element = &self.list[*index];
+                let mut variables = vec![("i", index.to_string()), ("a[i]", element.value.to_string())];
+                let current = self.nodes.get(*node);
+                if let Some(current) = current {
+                    variables.push(("node", current.element.value.to_string()));
+                }
+
+                // Walking on down the tree, or hanging the element off the
+                // node once there's nowhere left to walk
+                let walking = current.is_some_and(|current| {
+                    let child = if *element < current.element { current.left } else { current.right };
+                    child.is_some()
+                });
+                (if walking { 4 } else { 5 }, variables)
+            },
+            Phase::Traverse(.., written) => (7, vec![("written", written.to_string())]),
+            Phase::Done => return Some(Pseudocode { lines: PSEUDOCODE, line: None, variables: vec![] }),
+        };
+
+        Some(Pseudocode { lines: PSEUDOCODE, line: Some(line), variables })
+    }
+
+    fn set_list(&mut self, list: Vec<Vec<Element<T>>>) {
+        self.list = list.into_iter().flatten().collect();
+        self.nodes = vec![];
+        self.phase = Phase::Insert(0, 0);
+    }
+
+    fn get_delay(&self) -> time::Duration {
+        self.default_delay
+    }
+
+    fn step(&mut self) {
+        self.accesses = Accesses::default();
+        match &mut self.phase {
+            Phase::Insert(index, node) => {
+                if *index == self.list.len() {
+                    let root = if self.nodes.is_empty() { None } else { Some(0) };
+                    self.phase = Phase::Traverse(vec![], root, None, 0);
+                    return;
+                }
+
+                let element = self.list[*index].clone();
+                self.accesses.read((0, *index));
+                if !self.nodes.is_empty() {
+                    // Equal values go to the right so that the sort is stable
+                    let current = &self.nodes[*node];
+                    self.accesses.comparisons += 1;
+                    let goes_left = element < current.element;
+                    let child = if goes_left { current.left } else { current.right };
+
+                    if let Some(child) = child {
+                        *node = child;
+                        return;
+                    }
+
+                    let new_node = Some(self.nodes.len());
+                    if goes_left {
+                        self.nodes[*node].left = new_node;
+                    } else {
+                        self.nodes[*node].right = new_node;
+                    }
+                    self.accesses.writes_aside += 1;
+                }
+                self.nodes.push(Node { element, left: None, right: None });
+                self.accesses.writes_aside += 1;
+
+                *index += 1;
+                *node = 0;
+            },
+            Phase::Traverse(stack, next, last, written) => {
+                while let Some(node) = *next {
+                    stack.push(node);
+                    *next = self.nodes[node].left;
+                }
+
+                // The stack is only empty once every node has been written
+                let Some(node) = stack.pop() else {
+                    self.phase = Phase::Done;
+                    return;
+                };
+                self.list[*written] = self.nodes[node].element.clone();
+                self.accesses.write((0, *written));
+                *written += 1;
+                *next = self.nodes[node].right;
+                *last = Some(node);
+            },
+            Phase::Done => {},
+        }
+    }
+
+    fn get_accesses(&self) -> Accesses {
+        self.accesses.clone()
+    }
+}